@@ -0,0 +1,376 @@
+use super::*;
+
+const RATIO: Ratio = Ratio { decoded: 4, encoded: 5 };
+
+/// Ascii85 encoding.
+///
+/// Every group of four bytes is encoded as five characters in the range `!` to `u`.
+/// A final partial group of `n` bytes is encoded as `n + 1` characters.
+///
+/// A group of four zero bytes is encoded as `z`, the btoa flavour also encodes four spaces as `y`.
+/// Because of this the [`RATIO`](Encoding::RATIO) is only an upper bound for encoding.
+/// The decoded length may exceed [`Ratio::estimate_decoded_len`], decoding sizes its buffer with [`Ascii85::estimate_decoded_len`] instead.
+/// Decoding must be done in one go, it is not supported by the [`incremental`] decoder.
+///
+/// Decoding ignores ASCII whitespace.
+///
+/// See [`Ascii85::delimited`] for the `<~` and `~>` delimiters used by PostScript and PDF.
+#[derive(Clone, Debug)]
+pub struct Ascii85 {
+	spaces: bool,
+}
+
+impl Encoding for Ascii85 {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self, buffer)
+	}
+}
+
+impl Ascii85 {
+	/// Encodes the input bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let encoded = basenc::Ascii85Adobe.encode(b"hell\0\0\0\0");
+	/// assert_eq!(encoded, "BOu!rz");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode(bytes, self, std::string::String::new())
+	}
+
+	/// Decodes the input string.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let decoded = basenc::Ascii85Adobe.decode("BOu!r\nz").unwrap();
+	/// assert_eq!(decoded, b"hell\0\0\0\0");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode(string.as_bytes(), self, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut stack_buf = [0u8; 16];
+	/// let encoded = basenc::Ascii85Btoa.encode_into(b"    hello", &mut stack_buf);
+	/// assert_eq!(encoded, "yBOu!rDZ");
+	/// ```
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`Ascii85::estimate_decoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut stack_buf = [0u8; 16];
+	/// let decoded = basenc::Ascii85Btoa.decode_into("yBOu!rDZ", &mut stack_buf).unwrap();
+	/// assert_eq!(decoded, b"    hello");
+	/// ```
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode(string.as_bytes(), self, buffer)
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the input string.
+	///
+	/// Accounts for the `z` and `y` shortcuts in the string.
+	#[inline]
+	pub fn estimate_decoded_len(&self, string: &str) -> usize {
+		estimate_decoded_len(string.as_bytes(), self)
+	}
+
+	/// Wraps the encoding and bytes for display.
+	#[inline]
+	pub fn display<'a>(&'a self, bytes: &'a [u8]) -> Display<'a, Self> {
+		Display::new(self, bytes)
+	}
+
+	/// With `<~` and `~>` delimiters.
+	#[inline]
+	pub const fn delimited(&self) -> Ascii85Delimited<'_> {
+		Ascii85Delimited { encoding: self }
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Ascii85 encoding with delimiters.
+///
+/// Encoding wraps the output in `<~` and `~>`.
+/// Decoding accepts an optional `<~` and requires the `~>` end marker, surrounding whitespace is ignored.
+///
+/// # Examples
+///
+/// ```
+/// let encoded = basenc::Ascii85Adobe.delimited().encode(b"hello world");
+/// assert_eq!(encoded, "<~BOu!rD]j7BEbo7~>");
+///
+/// let decoded = basenc::Ascii85Adobe.delimited().decode("BOu!rD]j7BEbo7~>").unwrap();
+/// assert_eq!(decoded, b"hello world");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Ascii85Delimited<'a> {
+	encoding: &'a Ascii85,
+}
+
+impl Ascii85Delimited<'_> {
+	/// Estimates the maximum length of the encoded string given the length of the input bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		match RATIO.estimate_encoded_len(len).checked_add(4) {
+			Some(len) => len,
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Encodes the input bytes.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode_delimited(bytes, self.encoding, std::string::String::new())
+	}
+
+	/// Decodes the input string.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode_delimited(string.as_bytes(), self.encoding, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// The buffer must have room for [`Ascii85Delimited::estimate_encoded_len`] bytes.
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode_delimited(bytes, self.encoding, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`Ascii85::estimate_decoded_len`] bytes.
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode_delimited(string.as_bytes(), self.encoding, buffer)
+	}
+
+	/// Wraps the encoding and bytes for display.
+	#[inline]
+	pub fn display<'a>(&'a self, bytes: &'a [u8]) -> Display<'a, Self> {
+		Display { encoding: self, bytes }
+	}
+}
+
+impl<'a> fmt::Display for Display<'a, Ascii85Delimited<'_>> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("<~")?;
+		fmt::Display::fmt(&Display::new(self.encoding.encoding, self.bytes), f)?;
+		f.write_str("~>")
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Ascii85 Adobe flavour.
+///
+/// Four zero bytes are encoded as `z`.
+#[allow(non_upper_case_globals)]
+pub static Ascii85Adobe: Ascii85 = Ascii85 { spaces: false };
+
+/// Ascii85 btoa flavour.
+///
+/// Four zero bytes are encoded as `z` and four spaces as `y`.
+#[allow(non_upper_case_globals)]
+pub static Ascii85Btoa: Ascii85 = Ascii85 { spaces: true };
+
+//----------------------------------------------------------------
+// Encoding
+
+const SPACES: u32 = 0x20202020;
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], base: &Ascii85, mut buffer: B) -> B::Output {
+	let dest_len = RATIO.estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, base, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+#[inline(never)]
+fn encode_delimited<B: EncodeBuf>(bytes: &[u8], base: &Ascii85, mut buffer: B) -> B::Output {
+	let dest_len = Ascii85Delimited::estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		ptr::copy_nonoverlapping(b"<~".as_ptr(), dest, 2);
+		let end = encode_bytes(bytes, base, dest.add(2));
+		ptr::copy_nonoverlapping(b"~>".as_ptr(), end, 2);
+		let len = end.offset_from(dest) as usize + 2;
+		buffer.commit(len)
+	}
+}
+
+#[inline]
+unsafe fn encode_word(mut word: u32, dest: *mut u8) {
+	for i in (0..5).rev() {
+		*dest.add(i) = b'!' + (word % 85) as u8;
+		word /= 85;
+	}
+}
+
+unsafe fn encode_bytes(bytes: &[u8], base: &Ascii85, mut dest: *mut u8) -> *mut u8 {
+	let mut chunks = bytes.chunks_exact(4);
+	for chunk in &mut chunks {
+		let word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+		if word == 0 {
+			*dest = b'z';
+			dest = dest.add(1);
+		}
+		else if word == SPACES && base.spaces {
+			*dest = b'y';
+			dest = dest.add(1);
+		}
+		else {
+			encode_word(word, dest);
+			dest = dest.add(5);
+		}
+	}
+
+	// Pad the final group with zeros and drop the extra characters
+	let rest = chunks.remainder();
+	if !rest.is_empty() {
+		let mut group = [0u8; 4];
+		group[..rest.len()].copy_from_slice(rest);
+		let mut chars = [0u8; 5];
+		encode_word(u32::from_be_bytes(group), chars.as_mut_ptr());
+		ptr::copy_nonoverlapping(chars.as_ptr(), dest, rest.len() + 1);
+		dest = dest.add(rest.len() + 1);
+	}
+
+	return dest;
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+fn estimate_decoded_len(string: &[u8], base: &Ascii85) -> usize {
+	let shortcuts = string.iter().filter(|&&chr| chr == b'z' || chr == b'y' && base.spaces).count();
+	match shortcuts.checked_mul(4) {
+		Some(len) => match len.checked_add(RATIO.estimate_decoded_len(string.len())) {
+			Some(len) => len,
+			None => ratio::panic_overflow(),
+		},
+		None => ratio::panic_overflow(),
+	}
+}
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], base: &Ascii85, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = estimate_decoded_len(string, base);
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+#[inline(never)]
+fn decode_delimited<B: DecodeBuf>(string: &[u8], base: &Ascii85, buffer: B) -> Result<B::Output, Error> {
+	let start = string.iter().position(|&chr| !chr.is_ascii_whitespace()).unwrap_or(string.len());
+	let end = string.iter().rposition(|&chr| !chr.is_ascii_whitespace()).map_or(start, |i| i + 1);
+
+	let mut inner = &string[start..end];
+	let mut offset = start;
+	if inner.starts_with(b"<~") {
+		inner = &inner[2..];
+		offset += 2;
+	}
+	let Some(inner) = inner.strip_suffix(b"~>")
+	else {
+		return Err(Error::IncorrectLength);
+	};
+
+	decode(inner, base, buffer).map_err(|err| err.offset_by(offset))
+}
+
+unsafe fn decode_string(string: &[u8], base: &Ascii85, mut dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut word = 0u32;
+	let mut group_len = 0;
+
+	for (offset, &byte) in string.iter().enumerate() {
+		match byte {
+			b'!'..=b'u' => {
+				let digit = (byte - b'!') as u32;
+				word = match word.checked_mul(85).and_then(|word| word.checked_add(digit)) {
+					Some(word) => word,
+					None => return Err(Error::InvalidCharacter { offset, byte }),
+				};
+				group_len += 1;
+				if group_len == 5 {
+					(dest as *mut [u8; 4]).write_unaligned(word.to_be_bytes());
+					dest = dest.add(4);
+					word = 0;
+					group_len = 0;
+				}
+			},
+			b'z' if group_len == 0 => {
+				(dest as *mut [u8; 4]).write_unaligned([0; 4]);
+				dest = dest.add(4);
+			},
+			b'y' if group_len == 0 && base.spaces => {
+				(dest as *mut [u8; 4]).write_unaligned(SPACES.to_be_bytes());
+				dest = dest.add(4);
+			},
+			_ if byte.is_ascii_whitespace() => (),
+			_ => return Err(Error::InvalidCharacter { offset, byte }),
+		}
+	}
+
+	// Pad the final group with `u` and drop the extra bytes
+	if group_len > 0 {
+		if group_len == 1 {
+			return Err(Error::IncorrectLength);
+		}
+		for _ in group_len..5 {
+			word = match word.checked_mul(85).and_then(|word| word.checked_add(84)) {
+				Some(word) => word,
+				None => return Err(Error::IncorrectLength),
+			};
+		}
+		ptr::copy_nonoverlapping(word.to_be_bytes().as_ptr(), dest, group_len - 1);
+		dest = dest.add(group_len - 1);
+	}
+
+	Ok(dest)
+}
@@ -37,7 +37,7 @@ impl Base32 {
 
 	/// With explicit padding policy.
 	pub const fn pad(&self, pad: Padding) -> WithPad<'_, Self> {
-		WithPad { encoding: self, pad }
+		WithPad::new(self, pad)
 	}
 }
 
@@ -51,7 +51,7 @@ impl Encoding for Base32 {
 
 	#[inline]
 	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
-		decode(string, self, Padding::Optional, buffer)
+		decode(string, self, Padding::Optional, Mode::Normal, buffer)
 	}
 }
 
@@ -65,11 +65,12 @@ impl Encoding for WithPad<'_, Base32> {
 
 	#[inline]
 	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
-		decode(string, self.encoding, self.pad, buffer)
+		decode(string, self.encoding, self.pad, self.mode, buffer)
 	}
 }
 
 impl_encoding!(Base32);
+impl_whitespace!(Base32);
 
 //----------------------------------------------------------------
 
@@ -114,13 +115,30 @@ fn encode<B: EncodeBuf>(bytes: &[u8], base: &Base32, pad: Padding, mut buffer: B
 mod decode;
 
 #[inline(never)]
-fn decode<B: DecodeBuf>(string: &[u8], base: &Base32, pad: Padding, mut buffer: B) -> Result<B::Output, Error> {
+fn decode<B: DecodeBuf>(string: &[u8], base: &Base32, pad: Padding, mode: Mode, mut buffer: B) -> Result<B::Output, Error> {
 	let dest_len = RATIO.estimate_decoded_len(string.len());
 
 	unsafe {
 		let dest = buffer.allocate(dest_len);
-		let end = decode::decode(string, base, pad, dest)?;
+		let end = decode::decode(string, base, pad, mode, dest)?;
 		let len = end.offset_from(dest) as usize;
 		Ok(buffer.commit(len))
 	}
 }
+
+//----------------------------------------------------------------
+// Constant-time
+
+mod ct;
+
+//----------------------------------------------------------------
+// Crockford
+
+mod crockford;
+pub use self::crockford::{Base32Crockford, Base32CrockfordCheck};
+
+//----------------------------------------------------------------
+// Bech32
+
+mod bech32;
+pub use self::bech32::{Bech32, Bech32Std, Bech32m};
@@ -0,0 +1,444 @@
+use super::*;
+
+/// Bech32 encoding as specified by BIP-173 and BIP-350.
+///
+/// A Bech32 string consists of a human-readable part, the separator `1`, the data encoded as 5-bit values and a six character checksum.
+/// Strings are encoded in lowercase, decoding accepts all lowercase or all uppercase strings.
+///
+/// The data can be passed as bytes, regrouped into 5-bit values, as bytes with a leading version value as used by segwit addresses,
+/// or as raw 5-bit values as used by Lightning invoices.
+///
+/// Bech32 has a variable length and does not implement [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// let program = b"\x75\x1e\x76\xe8\x19\x91\x96\xd4\x54\x94\x1c\x45\xd1\xb3\xa3\x23\xf1\x43\x3b\xd6";
+///
+/// let address = basenc::Bech32Std.encode_version("bc", 0, program);
+/// assert_eq!(address, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+///
+/// let (hrp, version, decoded) = basenc::Bech32Std.decode_version(&address).unwrap();
+/// assert_eq!((hrp, version), ("bc", 0));
+/// assert_eq!(decoded, program);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Bech32 {
+	constant: u32,
+	limit: usize,
+}
+
+impl Bech32 {
+	/// Creates a new Bech32 encoding with the given checksum constant.
+	///
+	/// The length of the strings is limited to 90 characters.
+	#[inline]
+	pub const fn new(constant: u32) -> Self {
+		Bech32 { constant, limit: 90 }
+	}
+
+	/// With a different limit on the length of the strings.
+	///
+	/// Lightning invoices exceed the default limit of 90 characters.
+	/// Note that the checksum only guarantees to detect up to four errors in strings of up to 90 characters.
+	#[inline]
+	pub const fn limit(&self, limit: usize) -> Self {
+		Bech32 { constant: self.constant, limit }
+	}
+
+	/// Estimates the maximum length of the encoded string given the length of the human-readable part and the data in bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(hrp_len: usize, len: usize) -> usize {
+		// The separator, version value and checksum
+		match hrp_len.checked_add(8) {
+			Some(n) => match n.checked_add(RATIO.estimate_encoded_len(len)) {
+				Some(n) => n,
+				None => ratio::panic_overflow(),
+			},
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded data given the length of the input string.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		len
+	}
+
+	/// Encodes the human-readable part and the data bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the human-readable part is invalid or the string exceeds the length limit.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, hrp: &str, bytes: &[u8]) -> std::string::String {
+		self.encode_into(hrp, bytes, std::string::String::new())
+	}
+
+	/// Decodes the input string into the human-readable part and the data bytes.
+	///
+	/// The human-readable part is returned in the case of the input string.
+	///
+	/// Returns [`Error::ChecksumMismatch`] if the checksum does not match, see [`Bech32::locate_errors`] to find the likely positions of the errors.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode<'s>(&self, string: &'s str) -> Result<(&'s str, std::vec::Vec<u8>), Error> {
+		self.decode_into(string, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// The buffer must have room for [`Bech32::estimate_encoded_len`] bytes.
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, hrp: &str, bytes: &[u8], buffer: B) -> B::Output {
+		encode(self, hrp.as_bytes(), &[], bytes, true, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`Bech32::estimate_decoded_len`] bytes.
+	#[inline]
+	pub fn decode_into<'s, B: DecodeBuf>(&self, string: &'s str, buffer: B) -> Result<(&'s str, B::Output), Error> {
+		let (hrp, _, output) = decode(self, string, false, true, buffer)?;
+		Ok((hrp, output))
+	}
+
+	/// Encodes the human-readable part, a version value and the data bytes.
+	///
+	/// Segwit addresses use version 0 with [`Bech32Std`] and versions 1 to 16 with [`Bech32m`].
+	///
+	/// # Panics
+	///
+	/// Panics if the human-readable part is invalid, the version is not less than 32 or the string exceeds the length limit.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode_version(&self, hrp: &str, version: u8, bytes: &[u8]) -> std::string::String {
+		self.encode_version_into(hrp, version, bytes, std::string::String::new())
+	}
+
+	/// Decodes the input string into the human-readable part, a version value and the data bytes.
+	///
+	/// Returns [`Error::IncorrectLength`] if there is no version value.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode_version<'s>(&self, string: &'s str) -> Result<(&'s str, u8, std::vec::Vec<u8>), Error> {
+		self.decode_version_into(string, std::vec::Vec::new())
+	}
+
+	/// Encodes a version value and the data bytes into a buffer.
+	///
+	/// The buffer must have room for [`Bech32::estimate_encoded_len`] bytes.
+	#[inline]
+	pub fn encode_version_into<B: EncodeBuf>(&self, hrp: &str, version: u8, bytes: &[u8], buffer: B) -> B::Output {
+		encode(self, hrp.as_bytes(), &[version], bytes, true, buffer)
+	}
+
+	/// Decodes a version value and the data bytes into a buffer.
+	///
+	/// The buffer must have room for [`Bech32::estimate_decoded_len`] bytes.
+	#[inline]
+	pub fn decode_version_into<'s, B: DecodeBuf>(&self, string: &'s str, buffer: B) -> Result<(&'s str, u8, B::Output), Error> {
+		decode(self, string, true, true, buffer)
+	}
+
+	/// Encodes the human-readable part and raw 5-bit values.
+	///
+	/// # Panics
+	///
+	/// Panics if the human-readable part is invalid, a value is not less than 32 or the string exceeds the length limit.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode_u5(&self, hrp: &str, values: &[u8]) -> std::string::String {
+		self.encode_u5_into(hrp, values, std::string::String::new())
+	}
+
+	/// Decodes the input string into the human-readable part and raw 5-bit values.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode_u5<'s>(&self, string: &'s str) -> Result<(&'s str, std::vec::Vec<u8>), Error> {
+		self.decode_u5_into(string, std::vec::Vec::new())
+	}
+
+	/// Encodes raw 5-bit values into a buffer.
+	///
+	/// The buffer must have room for the length of the human-readable part and the values plus seven bytes.
+	#[inline]
+	pub fn encode_u5_into<B: EncodeBuf>(&self, hrp: &str, values: &[u8], buffer: B) -> B::Output {
+		encode(self, hrp.as_bytes(), &[], values, false, buffer)
+	}
+
+	/// Decodes raw 5-bit values into a buffer.
+	///
+	/// The buffer must have room for [`Bech32::estimate_decoded_len`] bytes.
+	#[inline]
+	pub fn decode_u5_into<'s, B: DecodeBuf>(&self, string: &'s str, buffer: B) -> Result<(&'s str, B::Output), Error> {
+		let (hrp, _, output) = decode(self, string, false, false, buffer)?;
+		Ok((hrp, output))
+	}
+
+	/// Locates the likely positions of errors in a string which fails the checksum.
+	///
+	/// Returns the offsets of up to two substituted characters in the data part which restore the checksum.
+	/// Returns `None` if the checksum matches, the string is malformed or longer than 90 characters, or no such substitution exists.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let string = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+	/// assert_eq!(basenc::Bech32Std.locate_errors(string), None);
+	///
+	/// let string = "bc1qw508d6qejxtdg4y5r3zarvaxy0c5xw7kv8f3t4";
+	/// assert_eq!(basenc::Bech32Std.locate_errors(string), Some((27, None)));
+	///
+	/// let string = "bc1qw508d6qejxtdg4y5r3zarvaxy0c5xw7kv8f3t5";
+	/// assert_eq!(basenc::Bech32Std.locate_errors(string), Some((27, Some(41))));
+	/// ```
+	pub fn locate_errors(&self, string: &str) -> Option<(usize, Option<usize>)> {
+		locate_errors(self, string.as_bytes())
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Bech32 as specified by BIP-173.
+#[allow(non_upper_case_globals)]
+pub static Bech32Std: Bech32 = Bech32::new(1);
+
+/// Bech32m as specified by BIP-350.
+#[allow(non_upper_case_globals)]
+pub static Bech32m: Bech32 = Bech32::new(0x2bc830a3);
+
+//----------------------------------------------------------------
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+static BECH32: Base32 = bech32();
+
+// Case-insensitive, mixed case is rejected separately
+const fn bech32() -> Base32 {
+	let mut base = Base32::new(CHARSET);
+	let mut i = 0;
+	while i < CHARSET.len() {
+		base.lut[CHARSET[i].to_ascii_uppercase() as usize] = i as u8;
+		i += 1;
+	}
+	base
+}
+
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+#[inline]
+fn polymod(chk: u32, value: u8) -> u32 {
+	let top = chk >> 25;
+	let mut chk = (chk & 0x1ffffff) << 5 ^ value as u32;
+	for (i, &generator) in GENERATOR.iter().enumerate() {
+		if top >> i & 1 != 0 {
+			chk ^= generator;
+		}
+	}
+	chk
+}
+
+// Checksum state after the expanded human-readable part
+#[inline]
+fn polymod_hrp(hrp: &[u8]) -> u32 {
+	let mut chk = 1;
+	for &chr in hrp {
+		chk = polymod(chk, chr.to_ascii_lowercase() >> 5);
+	}
+	chk = polymod(chk, 0);
+	for &chr in hrp {
+		chk = polymod(chk, chr.to_ascii_lowercase() & 0x1f);
+	}
+	chk
+}
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bech: &Bech32, hrp: &[u8], prefix: &[u8], data: &[u8], convert: bool, mut buffer: B) -> B::Output {
+	assert!(!hrp.is_empty() && hrp.iter().all(|&chr| matches!(chr, 33..=126)), "invalid Bech32 human-readable part");
+	assert!(prefix.iter().all(|&value| value < 32), "Bech32 value out of range");
+	assert!(convert || data.iter().all(|&value| value < 32), "Bech32 value out of range");
+
+	let data_len = if convert { (data.len() * 8).div_ceil(5) } else { data.len() };
+	let dest_len = hrp.len() + 1 + prefix.len() + data_len + 6;
+	assert!(dest_len <= bech.limit, "Bech32 string exceeds the length limit");
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let mut end = dest;
+		for &chr in hrp {
+			*end = chr.to_ascii_lowercase();
+			end = end.add(1);
+		}
+		*end = b'1';
+		end = end.add(1);
+
+		let start = end;
+		for &value in prefix {
+			*end = CHARSET[value as usize];
+			end = end.add(1);
+		}
+		if convert {
+			// Regroup the bytes into 5-bit values, the final value is padded with zero bits
+			end = encode::encode(data, &BECH32, Padding::None, end);
+		}
+		else {
+			for &value in data {
+				*end = CHARSET[value as usize];
+				end = end.add(1);
+			}
+		}
+
+		let mut chk = polymod_hrp(hrp);
+		for &chr in slice::from_raw_parts(start, end.offset_from(start) as usize) {
+			chk = polymod(chk, BECH32.lut[chr as usize]);
+		}
+		for _ in 0..6 {
+			chk = polymod(chk, 0);
+		}
+
+		chk ^= bech.constant;
+		for i in 0..6 {
+			*end = CHARSET[(chk >> (5 * (5 - i)) & 0x1f) as usize];
+			end = end.add(1);
+		}
+
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+// Validates the string and returns the checksum residue and the position of the separator
+fn parse(bech: &Bech32, string: &[u8]) -> Result<(u32, usize), Error> {
+	if string.len() > bech.limit {
+		return Err(Error::IncorrectLength);
+	}
+
+	// Mixed case is not allowed
+	let mut upper = None;
+	for (offset, &byte) in string.iter().enumerate() {
+		if !matches!(byte, 33..=126) {
+			return Err(Error::InvalidCharacter { offset, byte });
+		}
+		if byte.is_ascii_alphabetic() && *upper.get_or_insert(byte.is_ascii_uppercase()) != byte.is_ascii_uppercase() {
+			return Err(Error::InvalidCharacter { offset, byte });
+		}
+	}
+
+	let separator = match string.iter().rposition(|&chr| chr == b'1') {
+		Some(separator) if separator > 0 && string.len() - separator > 6 => separator,
+		_ => return Err(Error::IncorrectLength),
+	};
+
+	let mut chk = polymod_hrp(&string[..separator]);
+	for (offset, &byte) in string.iter().enumerate().skip(separator + 1) {
+		let value = if byte < 128 { BECH32.lut[byte as usize] } else { 255 };
+		if value >= 32 {
+			return Err(Error::InvalidCharacter { offset, byte });
+		}
+		chk = polymod(chk, value);
+	}
+
+	Ok((chk ^ bech.constant, separator))
+}
+
+#[inline(never)]
+fn decode<'s, B: DecodeBuf>(bech: &Bech32, string: &'s str, version: bool, convert: bool, mut buffer: B) -> Result<(&'s str, u8, B::Output), Error> {
+	let (residue, separator) = parse(bech, string.as_bytes())?;
+	if residue != 0 {
+		return Err(Error::ChecksumMismatch);
+	}
+
+	let hrp = &string[..separator];
+	let mut start = separator + 1;
+	let data = &string.as_bytes()[..string.len() - 6];
+
+	let version = if version {
+		if start == data.len() {
+			return Err(Error::IncorrectLength);
+		}
+		start += 1;
+		BECH32.lut[data[start - 1] as usize]
+	}
+	else {
+		0
+	};
+
+	let dest_len = Bech32::estimate_decoded_len(data.len() - start);
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let mut end = dest;
+
+		if convert {
+			// The padding must be less than 5 bits and all zeros
+			end = decode::decode(&data[start..], &BECH32, Padding::None, Mode::Normal, dest).map_err(|err| err.offset_by(start))?;
+		}
+		else {
+			for &byte in &data[start..] {
+				*end = BECH32.lut[byte as usize];
+				end = end.add(1);
+			}
+		}
+
+		let len = end.offset_from(dest) as usize;
+		Ok((hrp, version, buffer.commit(len)))
+	}
+}
+
+//----------------------------------------------------------------
+// Error location
+
+// Longest data part for which errors are located
+const MAX_LOCATE: usize = 90;
+
+#[inline(never)]
+fn locate_errors(bech: &Bech32, string: &[u8]) -> Option<(usize, Option<usize>)> {
+	if string.len() > MAX_LOCATE {
+		return None;
+	}
+	let (residue, separator) = parse(bech, string).ok()?;
+	if residue == 0 {
+		return None;
+	}
+
+	// The checksum is linear, an error `e` at distance `d` from the end changes the residue by `syndromes[d][e]`
+	let n = string.len() - separator - 1;
+	let mut syndromes = [[0u32; 32]; MAX_LOCATE];
+	for e in 1..32 {
+		let mut chk = polymod(0, e as u8);
+		for row in &mut syndromes[..n] {
+			row[e] = chk;
+			chk = polymod(chk, 0);
+		}
+	}
+	let offset = |d: usize| string.len() - 1 - d;
+
+	// Single substitution
+	if let Some(d) = syndromes[..n].iter().position(|row| row.contains(&residue)) {
+		return Some((offset(d), None));
+	}
+
+	// Two substitutions
+	for d1 in 0..n {
+		for e1 in 1..32 {
+			let target = residue ^ syndromes[d1][e1];
+			if let Some(i) = syndromes[d1 + 1..n].iter().position(|row| row[1..].contains(&target)) {
+				return Some((offset(d1 + 1 + i), Some(offset(d1))));
+			}
+		}
+	}
+
+	None
+}
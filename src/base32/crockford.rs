@@ -0,0 +1,303 @@
+use super::*;
+
+/// Crockford's Base32 encoding.
+///
+/// Encodes with the alphabet `0-9A-HJKMNP-TV-Z` without padding.
+///
+/// Decoding is case-insensitive, accepts `O` for `0` and `I` and `L` for `1` and ignores hyphens.
+/// The [`Encoding`] implementation, used by the incremental decoder and the whitespace adapters, rejects hyphens.
+///
+/// See [`Base32Crockford::check`] to append a check symbol.
+#[derive(Clone, Debug)]
+pub struct Base32Crockford;
+
+impl Encoding for Base32Crockford {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, &CROCKFORD, Padding::None, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, &CROCKFORD, Padding::None, Mode::Normal, buffer)
+	}
+}
+
+impl Base32Crockford {
+	/// Encodes the input bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let encoded = basenc::Base32Crockford.encode(b"hello world");
+	/// assert_eq!(encoded, "D1JPRV3F41VPYWKCCG");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		Encoding::encode_into(self, bytes, std::string::String::new())
+	}
+
+	/// Decodes the input string, ignoring hyphens.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let decoded = basenc::Base32Crockford.decode("d1jprv3f-41vpywkccg").unwrap();
+	/// assert_eq!(decoded, b"hello world");
+	///
+	/// let decoded = basenc::Base32Crockford.decode("D1JPRV3F-4IVPYWKCCG").unwrap();
+	/// assert_eq!(decoded, b"hello world");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode_crockford(string.as_bytes(), std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut stack_buf = [0u8; 16];
+	/// let encoded = basenc::Base32Crockford.encode_into(b"hello", &mut stack_buf);
+	/// assert_eq!(encoded, "D1JPRV3F");
+	/// ```
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		Encoding::encode_into(self, bytes, buffer)
+	}
+
+	/// Decodes into a buffer, ignoring hyphens.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let decoded = basenc::Base32Crockford.decode_into("D1JP-RV3F", Vec::new()).unwrap();
+	/// assert_eq!(decoded, b"hello");
+	/// ```
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode_crockford(string.as_bytes(), buffer)
+	}
+
+	/// Wraps the encoding and bytes for display.
+	#[inline]
+	pub fn display<'a>(&'a self, bytes: &'a [u8]) -> Display<'a, Self> {
+		Display::new(self, bytes)
+	}
+}
+
+impl_whitespace!(Base32Crockford);
+
+//----------------------------------------------------------------
+
+/// Crockford's Base32 encoding with check symbol.
+///
+/// A check symbol is appended, the input bytes taken as a big-endian number modulo 37.
+/// The check symbols beyond the alphabet are `*~$=U`.
+///
+/// # Examples
+///
+/// ```
+/// let encoded = basenc::Base32Crockford.check().encode(b"hello world");
+/// assert_eq!(encoded, "D1JPRV3F41VPYWKCCGS");
+///
+/// let decoded = basenc::Base32Crockford.check().decode("d1jp-rv3f-41vp-ywkc-cgs").unwrap();
+/// assert_eq!(decoded, b"hello world");
+///
+/// let result = basenc::Base32Crockford.check().decode("D1JPRV3F41VPYWKCCG=");
+/// assert_eq!(result, Err(basenc::Error::ChecksumMismatch));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Base32CrockfordCheck;
+
+impl Base32Crockford {
+	/// With check symbol.
+	#[inline]
+	pub const fn check(&self) -> Base32CrockfordCheck {
+		Base32CrockfordCheck
+	}
+}
+
+impl Base32CrockfordCheck {
+	/// Estimates the maximum length of the encoded string given the length of the input bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		match RATIO.estimate_encoded_len(len).checked_add(1) {
+			Some(len) => len,
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the length of the input string.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		RATIO.estimate_decoded_len(len)
+	}
+
+	/// Encodes the input bytes and appends the check symbol.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		self.encode_into(bytes, std::string::String::new())
+	}
+
+	/// Decodes the input string and verifies the check symbol.
+	///
+	/// Returns [`Error::ChecksumMismatch`] if the check symbol does not match and [`Error::IncorrectLength`] if the input is empty.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		self.decode_into(string, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer and appends the check symbol.
+	///
+	/// The buffer must have room for [`Base32CrockfordCheck::estimate_encoded_len`] bytes.
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode_check(bytes, buffer)
+	}
+
+	/// Decodes into a buffer and verifies the check symbol.
+	///
+	/// The buffer must have room for [`Base32CrockfordCheck::estimate_decoded_len`] bytes.
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode_check(string.as_bytes(), buffer)
+	}
+}
+
+//----------------------------------------------------------------
+
+const CHARSET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+// Check symbols for the values 32 to 36
+const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+
+static CROCKFORD: Base32 = crockford();
+
+// Case-insensitive with aliases for the confusable characters
+const fn crockford() -> Base32 {
+	let mut base = Base32::new(CHARSET);
+	let mut i = 0;
+	while i < CHARSET.len() {
+		let chr = CHARSET[i];
+		base.lut[chr.to_ascii_lowercase() as usize] = i as u8;
+		i += 1;
+	}
+	base.lut[b'O' as usize] = 0;
+	base.lut[b'o' as usize] = 0;
+	base.lut[b'I' as usize] = 1;
+	base.lut[b'i' as usize] = 1;
+	base.lut[b'L' as usize] = 1;
+	base.lut[b'l' as usize] = 1;
+	base
+}
+
+#[inline]
+fn check_value(bytes: &[u8]) -> usize {
+	bytes.iter().fold(0, |acc, &byte| (acc * 256 + byte as usize) % 37)
+}
+
+#[inline]
+fn check_symbol(value: usize) -> u8 {
+	if value < 32 { CHARSET[value] } else { CHECK_SYMBOLS[value - 32] }
+}
+
+#[inline(never)]
+fn encode_check<B: EncodeBuf>(bytes: &[u8], mut buffer: B) -> B::Output {
+	let dest_len = Base32CrockfordCheck::estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode::encode(bytes, &CROCKFORD, Padding::None, dest);
+		*end = check_symbol(check_value(bytes));
+		let len = end.offset_from(dest) as usize + 1;
+		buffer.commit(len)
+	}
+}
+
+#[inline(never)]
+fn decode_crockford<B: DecodeBuf>(string: &[u8], mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = RATIO.estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+#[inline(never)]
+fn decode_check<B: DecodeBuf>(string: &[u8], mut buffer: B) -> Result<B::Output, Error> {
+	// The check symbol is the last character other than a hyphen
+	let Some(index) = string.iter().rposition(|&chr| chr != b'-') else {
+		return Err(Error::IncorrectLength);
+	};
+	let byte = string[index];
+	let value = match byte {
+		b'*' | b'~' | b'$' | b'=' => 32 + CHECK_SYMBOLS.iter().position(|&chr| chr == byte).unwrap(),
+		b'U' | b'u' => 36,
+		_ if byte < 128 && CROCKFORD.lut[byte as usize] < 32 => CROCKFORD.lut[byte as usize] as usize,
+		_ => return Err(Error::InvalidCharacter { offset: index, byte }),
+	};
+
+	let dest_len = RATIO.estimate_decoded_len(index);
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(&string[..index], dest)?;
+		let len = end.offset_from(dest) as usize;
+		if check_value(slice::from_raw_parts(dest, len)) != value {
+			return Err(Error::ChecksumMismatch);
+		}
+		Ok(buffer.commit(len))
+	}
+}
+
+unsafe fn decode_string(string: &[u8], mut dest: *mut u8) -> Result<*mut u8, Error> {
+	if !string.contains(&b'-') {
+		return decode::decode(string, &CROCKFORD, Padding::None, Mode::Normal, dest);
+	}
+
+	// Gather the characters between the hyphens in groups of eight
+	let mut group = Scrub([0u8; 8]);
+	let mut offsets = [0usize; 8];
+	let mut len = 0;
+	for (i, &byte) in string.iter().enumerate() {
+		if byte == b'-' {
+			continue;
+		}
+		group[len] = byte;
+		offsets[len] = i;
+		len += 1;
+		if len == group.len() {
+			dest = decode_group(&group[..len], &offsets, dest)?;
+			len = 0;
+		}
+	}
+	if len > 0 {
+		dest = decode_group(&group[..len], &offsets, dest)?;
+	}
+
+	Ok(dest)
+}
+
+#[inline]
+unsafe fn decode_group(group: &[u8], offsets: &[usize; 8], dest: *mut u8) -> Result<*mut u8, Error> {
+	decode::decode(group, &CROCKFORD, Padding::None, Mode::Normal, dest).map_err(|err| match err {
+		Error::InvalidCharacter { offset, byte } => Error::InvalidCharacter { offset: offsets[offset], byte },
+		Error::NonCanonical { offset } => Error::NonCanonical { offset: offsets[offset] },
+		err => err,
+	})
+}
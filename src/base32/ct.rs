@@ -0,0 +1,227 @@
+use super::*;
+use crate::ct::{eq, ConstantTime};
+
+impl ConstantTime<'_, Base32> {
+	/// With explicit padding policy.
+	pub const fn pad(&self, pad: Padding) -> WithPad<'_, Self> {
+		WithPad::new(self, pad)
+	}
+}
+
+impl Encoding for ConstantTime<'_, Base32> {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self.encoding, Padding::Optional, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self.encoding, Padding::Optional, Mode::Normal, buffer)
+	}
+}
+
+impl Encoding for WithPad<'_, ConstantTime<'_, Base32>> {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self.encoding.encoding, self.pad, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self.encoding.encoding, self.pad, self.mode, buffer)
+	}
+}
+
+impl_encoding!(ConstantTime<'_, Base32>);
+impl_whitespace!(ConstantTime<'_, Base32>);
+
+//----------------------------------------------------------------
+// Encoding
+
+// The charset is arbitrary, scan all of it
+#[inline]
+fn encode_value(value: u32, base: &Base32) -> u8 {
+	let mut chr = 0;
+	for (i, &c) in base.charset.iter().enumerate() {
+		chr |= eq(value, i as u32) & c as u32;
+	}
+	chr as u8
+}
+
+unsafe fn encode_bytes(bytes: &[u8], base: &Base32, pad: Padding, mut dest: *mut u8) -> *mut u8 {
+	for chunk in bytes.chunks(5) {
+		// aaaaabbb bbcccccd ddddeeee efffffgg ggghhhhh
+		let mut word = 0u64;
+		for (i, &byte) in chunk.iter().enumerate() {
+			word |= (byte as u64) << (32 - 8 * i);
+		}
+
+		let len = (chunk.len() * 8).div_ceil(5);
+		for i in 0..len {
+			*dest.add(i) = encode_value((word >> (35 - 5 * i)) as u32 & 0x1f, base);
+		}
+		dest = dest.add(len);
+
+		if matches!(pad, Padding::Strict) {
+			for _ in len..8 {
+				*dest = PAD_CHAR;
+				dest = dest.add(1);
+			}
+		}
+	}
+
+	return dest;
+}
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], base: &Base32, pad: Padding, mut buffer: B) -> B::Output {
+	let dest_len = RATIO.estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, base, pad, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+/// Returns the value and a mask with all bits set if the character is valid.
+#[inline]
+fn decode_char(chr: u8, base: &Base32) -> (u32, u32) {
+	let mut value = 0;
+	let mut valid = 0;
+	for (i, &c) in base.charset.iter().enumerate() {
+		let mask = eq(chr as u32, c as u32);
+		value |= mask & i as u32;
+		valid |= mask;
+	}
+	(value, valid)
+}
+
+// Decodes 2, 4, 5, 7 or 8 characters into 1 to 5 bytes
+#[inline]
+unsafe fn decode_chunk(chunk: &[u8], base: &Base32, mode: Mode, invalid: &mut u64, dest: *mut u8) -> *mut u8 {
+	let mut word = 0u64;
+	for (i, &chr) in chunk.iter().enumerate() {
+		let (value, valid) = decode_char(chr, base);
+		word |= (value as u64) << (35 - 5 * i);
+		*invalid |= !valid as u64;
+	}
+
+	let len = chunk.len() * 5 / 8;
+	if !matches!(mode, Mode::Lenient) {
+		// The trailing bits must be zero
+		*invalid |= word & ((1 << (40 - 8 * len)) - 1);
+	}
+
+	for i in 0..len {
+		*dest.add(i) = (word >> (32 - 8 * i)) as u8;
+	}
+	dest.add(len)
+}
+
+unsafe fn decode_string(string: &[u8], base: &Base32, pad: Padding, mode: Mode, dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut invalid = 0;
+	let mut rest = string;
+	let mut end = dest;
+
+	while rest.len() >= 8 {
+		let chunk = &rest[..8];
+
+		let padded = !matches!(pad, Padding::None) && chunk[7] == PAD_CHAR;
+		let len =
+			if !padded { 8 }
+			else if chunk[6] != PAD_CHAR || chunk[5] != PAD_CHAR { 7 }
+			else if chunk[4] != PAD_CHAR { 5 }
+			else if chunk[3] != PAD_CHAR || chunk[2] != PAD_CHAR { 4 }
+			else { 2 };
+		end = decode_chunk(&chunk[..len], base, mode, &mut invalid, end);
+
+		// Padding is only accepted in the final chunk
+		if padded && matches!(mode, Mode::Strict) && rest.len() > 8 {
+			invalid = !0;
+		}
+
+		rest = &rest[8..];
+	}
+
+	if rest.len() != 0 {
+		if matches!(pad, Padding::Strict) || !matches!(rest.len(), 2 | 4 | 5 | 7) {
+			invalid = !0;
+		}
+		else {
+			end = decode_chunk(rest, base, mode, &mut invalid, end);
+		}
+	}
+
+	if invalid != 0 {
+		return Err(locate(string, base, pad, mode));
+	}
+
+	Ok(end)
+}
+
+// Locates the error found above in the same order as the regular decoder, the input is not secret once rejected
+#[cold]
+fn locate(string: &[u8], base: &Base32, pad: Padding, mode: Mode) -> Error {
+	let mut offset = 0;
+	while string.len() - offset >= 8 {
+		let chunk = &string[offset..offset + 8];
+
+		let padded = !matches!(pad, Padding::None) && chunk[7] == PAD_CHAR;
+		let len =
+			if !padded { 8 }
+			else if chunk[6] != PAD_CHAR || chunk[5] != PAD_CHAR { 7 }
+			else if chunk[4] != PAD_CHAR { 5 }
+			else if chunk[3] != PAD_CHAR || chunk[2] != PAD_CHAR { 4 }
+			else { 2 };
+		if let Some(err) = locate_chunk(&chunk[..len], base, mode) {
+			return err.offset_by(offset);
+		}
+
+		if padded && matches!(mode, Mode::Strict) && string.len() - offset > 8 {
+			return Error::InvalidCharacter { offset: offset + len, byte: PAD_CHAR };
+		}
+
+		offset += 8;
+	}
+
+	let rest = &string[offset..];
+	if !matches!(pad, Padding::Strict) && matches!(rest.len(), 2 | 4 | 5 | 7) {
+		if let Some(err) = locate_chunk(rest, base, mode) {
+			return err.offset_by(offset);
+		}
+	}
+
+	// All characters are valid, the remaining characters are not a valid length or not padded
+	Error::IncorrectLength
+}
+
+fn locate_chunk(chunk: &[u8], base: &Base32, mode: Mode) -> Option<Error> {
+	if let Some(offset) = chunk.iter().position(|&chr| decode_char(chr, base).1 == 0) {
+		return Some(Error::InvalidCharacter { offset, byte: chunk[offset] });
+	}
+	let mut invalid = 0;
+	let mut scratch = Scrub([0u8; 5]);
+	unsafe { decode_chunk(chunk, base, mode, &mut invalid, scratch.as_mut_ptr()) };
+	if invalid != 0 { Some(Error::NonCanonical { offset: 0 }) } else { None }
+}
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], base: &Base32, pad: Padding, mode: Mode, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = RATIO.estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, pad, mode, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
@@ -1,13 +1,14 @@
 use super::*;
 
 #[inline]
-fn lookup(byte: u8, lut: &[u8; 128]) -> Result<u8, crate::Error> {
+fn lookup(chunk: &[u8], i: usize, lut: &[u8; 128]) -> Result<u8, crate::Error> {
+	let byte = chunk[i];
 	if byte as usize >= lut.len() {
-		return Err(crate::Error::InvalidCharacter);
+		return Err(crate::Error::InvalidCharacter { offset: i, byte });
 	}
 	let v = lut[byte as usize];
 	if v >= 32 {
-		return Err(crate::Error::InvalidCharacter);
+		return Err(crate::Error::InvalidCharacter { offset: i, byte });
 	}
 	Ok(v)
 }
@@ -15,14 +16,14 @@ fn lookup(byte: u8, lut: &[u8; 128]) -> Result<u8, crate::Error> {
 // aaaaabbb bbcccccd ddddeeee efffffgg ggghhhhh
 #[inline]
 unsafe fn decode_8bytes(chunk: &[u8; 8], base: &Base32, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
-	let c = lookup(chunk[2], &base.lut)?;
-	let d = lookup(chunk[3], &base.lut)?;
-	let e = lookup(chunk[4], &base.lut)?;
-	let f = lookup(chunk[5], &base.lut)?;
-	let g = lookup(chunk[6], &base.lut)?;
-	let h = lookup(chunk[7], &base.lut)?;
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
+	let c = lookup(chunk, 2, &base.lut)?;
+	let d = lookup(chunk, 3, &base.lut)?;
+	let e = lookup(chunk, 4, &base.lut)?;
+	let f = lookup(chunk, 5, &base.lut)?;
+	let g = lookup(chunk, 6, &base.lut)?;
+	let h = lookup(chunk, 7, &base.lut)?;
 
 	*dest.add(0) = a << 3 | b >> 2;
 	*dest.add(1) = b << 6 | c << 1 | d >> 4;
@@ -35,17 +36,17 @@ unsafe fn decode_8bytes(chunk: &[u8; 8], base: &Base32, dest: *mut u8) -> Result
 
 // aaaaabbb bbcccccd ddddeeee efffffgg 000-----
 #[inline]
-unsafe fn decode_7bytes(chunk: &[u8; 7], base: &Base32, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
-	let c = lookup(chunk[2], &base.lut)?;
-	let d = lookup(chunk[3], &base.lut)?;
-	let e = lookup(chunk[4], &base.lut)?;
-	let f = lookup(chunk[5], &base.lut)?;
-	let g = lookup(chunk[6], &base.lut)?;
-
-	if g & 0x7 != 0 {
-		return Err(crate::Error::NonCanonical);
+unsafe fn decode_7bytes(chunk: &[u8; 7], base: &Base32, mode: Mode, dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
+	let c = lookup(chunk, 2, &base.lut)?;
+	let d = lookup(chunk, 3, &base.lut)?;
+	let e = lookup(chunk, 4, &base.lut)?;
+	let f = lookup(chunk, 5, &base.lut)?;
+	let g = lookup(chunk, 6, &base.lut)?;
+
+	if !matches!(mode, Mode::Lenient) && g & 0x7 != 0 {
+		return Err(crate::Error::NonCanonical { offset: 0 });
 	}
 
 	*dest.add(0) = a << 3 | b >> 2;
@@ -58,15 +59,15 @@ unsafe fn decode_7bytes(chunk: &[u8; 7], base: &Base32, dest: *mut u8) -> Result
 
 // aaaaabbb bbcccccd ddddeeee 0------- --------
 #[inline]
-unsafe fn decode_5bytes(chunk: &[u8; 5], base: &Base32, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
-	let c = lookup(chunk[2], &base.lut)?;
-	let d = lookup(chunk[3], &base.lut)?;
-	let e = lookup(chunk[4], &base.lut)?;
-
-	if e & 0x1 != 0 {
-		return Err(crate::Error::NonCanonical);
+unsafe fn decode_5bytes(chunk: &[u8; 5], base: &Base32, mode: Mode, dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
+	let c = lookup(chunk, 2, &base.lut)?;
+	let d = lookup(chunk, 3, &base.lut)?;
+	let e = lookup(chunk, 4, &base.lut)?;
+
+	if !matches!(mode, Mode::Lenient) && e & 0x1 != 0 {
+		return Err(crate::Error::NonCanonical { offset: 0 });
 	}
 
 	*dest.add(0) = a << 3 | b >> 2;
@@ -78,14 +79,14 @@ unsafe fn decode_5bytes(chunk: &[u8; 5], base: &Base32, dest: *mut u8) -> Result
 
 // aaaaabbb bbcccccd 0000---- -------- --------
 #[inline]
-unsafe fn decode_4bytes(chunk: &[u8; 4], base: &Base32, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
-	let c = lookup(chunk[2], &base.lut)?;
-	let d = lookup(chunk[3], &base.lut)?;
-
-	if d & 0xf != 0 {
-		return Err(crate::Error::NonCanonical);
+unsafe fn decode_4bytes(chunk: &[u8; 4], base: &Base32, mode: Mode, dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
+	let c = lookup(chunk, 2, &base.lut)?;
+	let d = lookup(chunk, 3, &base.lut)?;
+
+	if !matches!(mode, Mode::Lenient) && d & 0xf != 0 {
+		return Err(crate::Error::NonCanonical { offset: 0 });
 	}
 
 	*dest.add(0) = a << 3 | b >> 2;
@@ -96,12 +97,12 @@ unsafe fn decode_4bytes(chunk: &[u8; 4], base: &Base32, dest: *mut u8) -> Result
 
 // aaaaabbb 00------ -------- -------- --------
 #[inline]
-unsafe fn decode_2bytes(chunk: &[u8; 2], base: &Base32, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
+unsafe fn decode_2bytes(chunk: &[u8; 2], base: &Base32, mode: Mode, dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
 
-	if b & 0x3 != 0 {
-		return Err(crate::Error::NonCanonical);
+	if !matches!(mode, Mode::Lenient) && b & 0x3 != 0 {
+		return Err(crate::Error::NonCanonical { offset: 0 });
 	}
 
 	*dest.add(0) = a << 3 | b >> 2;
@@ -109,30 +110,40 @@ unsafe fn decode_2bytes(chunk: &[u8; 2], base: &Base32, dest: *mut u8) -> Result
 	Ok(dest.add(1))
 }
 
-pub unsafe fn decode(mut string: &[u8], base: &Base32, pad: Padding, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+pub unsafe fn decode(mut string: &[u8], base: &Base32, pad: Padding, mode: Mode, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let input_len = string.len();
+
 	while string.len() >= 8 {
 		let chunk = &*(string.as_ptr() as *const [u8; 8]);
 
-		if !matches!(pad, Padding::None) && chunk[7] == PAD_CHAR {
+		let padded = !matches!(pad, Padding::None) && chunk[7] == PAD_CHAR;
+		let result = if padded {
 			if chunk[6] == PAD_CHAR && chunk[5] == PAD_CHAR {
 				if chunk[4] == PAD_CHAR {
 					if chunk[3] == PAD_CHAR && chunk[2] == PAD_CHAR {
-						dest = decode_2bytes(&*(chunk as *const _ as *const [u8; 2]), base, dest)?;
+						decode_2bytes(&*(chunk as *const _ as *const [u8; 2]), base, mode, dest)
 					}
 					else {
-						dest = decode_4bytes(&*(chunk as *const _ as *const [u8; 4]), base, dest)?;
+						decode_4bytes(&*(chunk as *const _ as *const [u8; 4]), base, mode, dest)
 					}
 				}
 				else {
-					dest = decode_5bytes(&*(chunk as *const _ as *const [u8; 5]), base, dest)?;
+					decode_5bytes(&*(chunk as *const _ as *const [u8; 5]), base, mode, dest)
 				}
 			}
 			else {
-				dest = decode_7bytes(&*(chunk as *const _ as *const [u8; 7]), base, dest)?;
+				decode_7bytes(&*(chunk as *const _ as *const [u8; 7]), base, mode, dest)
 			}
 		}
 		else {
-			dest = decode_8bytes(chunk, base, dest)?;
+			decode_8bytes(chunk, base, dest)
+		};
+		dest = result.map_err(|err| err.offset_by(input_len - string.len()))?;
+
+		// Padding is only accepted in the final chunk
+		if padded && matches!(mode, Mode::Strict) && string.len() > 8 {
+			let offset = input_len - string.len() + chunk.iter().position(|&chr| chr == PAD_CHAR).unwrap_or(7);
+			return Err(crate::Error::InvalidCharacter { offset, byte: PAD_CHAR });
 		}
 
 		string = &string[8..];
@@ -143,13 +154,14 @@ pub unsafe fn decode(mut string: &[u8], base: &Base32, pad: Padding, mut dest: *
 			return Err(crate::Error::IncorrectLength);
 		}
 
-		dest = match string.len() {
-			7 => decode_7bytes(&*(string.as_ptr() as *const [u8; 7]), base, dest)?,
-			5 => decode_5bytes(&*(string.as_ptr() as *const [u8; 5]), base, dest)?,
-			4 => decode_4bytes(&*(string.as_ptr() as *const [u8; 4]), base, dest)?,
-			2 => decode_2bytes(&*(string.as_ptr() as *const [u8; 2]), base, dest)?,
+		let result = match string.len() {
+			7 => decode_7bytes(&*(string.as_ptr() as *const [u8; 7]), base, mode, dest),
+			5 => decode_5bytes(&*(string.as_ptr() as *const [u8; 5]), base, mode, dest),
+			4 => decode_4bytes(&*(string.as_ptr() as *const [u8; 4]), base, mode, dest),
+			2 => decode_2bytes(&*(string.as_ptr() as *const [u8; 2]), base, mode, dest),
 			_ => return Err(crate::Error::IncorrectLength),
 		};
+		dest = result.map_err(|err| err.offset_by(input_len - string.len()))?;
 	}
 
 	Ok(dest)
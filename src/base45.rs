@@ -0,0 +1,190 @@
+use super::*;
+
+const RATIO: Ratio = Ratio { decoded: 2, encoded: 3 };
+
+/// Base45 alphabet as specified by RFC 9285.
+///
+/// Every two bytes are encoded as three characters, least significant digit first.
+/// A final odd byte is encoded as two characters.
+///
+/// The alphabet contains a space, Base45 does not provide the [`SkipWhitespace`] and [`Wrap`] adapters.
+///
+/// ```compile_fail
+/// let decoded = basenc::Base45Std.skip_whitespace().decode("A 1");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Base45 {
+	charset: [u8; 45],
+	lut: [u8; 128],
+}
+
+impl Base45 {
+	/// Creates a new Base45 alphabet.
+	///
+	/// Unlike the other alphabets the space character is allowed.
+	///
+	/// # Panics
+	///
+	/// Panics if the alphabet contains duplicate or non-ASCII characters.
+	pub const fn new(&charset: &[u8; 45]) -> Self {
+		let mut lut = [255; 128];
+		let mut i = 0;
+		while i < charset.len() {
+			if charset[i] as usize >= lut.len() {
+				panic!("non-ASCII character in Base45 charset");
+			}
+			if lut[charset[i] as usize] != 255 {
+				panic!("duplicate character in Base45 charset");
+			}
+			lut[charset[i] as usize] = i as u8;
+			i += 1;
+		}
+		Base45 { charset, lut }
+	}
+}
+
+impl Encoding for Base45 {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self, buffer)
+	}
+}
+
+impl_encoding!(Base45,
+	encode: [
+		"```",
+		"let encoded = basenc::Base45Std.encode(b\"Hello!!\");",
+		"assert_eq!(encoded, \"%69 VD92EX0\");",
+		"```",
+	],
+	decode: [
+		"```",
+		"let decoded = basenc::Base45Std.decode(\"QED8WEX0\").unwrap();",
+		"assert_eq!(decoded, b\"ietf!\");",
+		"",
+		"assert_eq!(basenc::Base45Std.decode(\"GGW\"), Err(basenc::Error::OutOfRange { offset: 0 }));",
+		"```",
+	],
+	encode_into: [
+		"```",
+		"let mut stack_buf = [0u8; 16];",
+		"let encoded = basenc::Base45Std.encode_into(b\"AB\", &mut stack_buf);",
+		"assert_eq!(encoded, \"BB8\");",
+		"```",
+	],
+	decode_into: [
+		"```",
+		"let decoded = basenc::Base45Std.decode_into(\"BB8\", Vec::new()).unwrap();",
+		"assert_eq!(decoded, b\"AB\");",
+		"```",
+	],
+);
+
+//----------------------------------------------------------------
+
+/// Base45 RFC 9285 alphabet.
+///
+/// The alphabet is `0-9A-Z $%*+-./:`, the QR code alphanumeric character set.
+#[allow(non_upper_case_globals)]
+pub static Base45Std: Base45 = Base45::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:");
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], base: &Base45, mut buffer: B) -> B::Output {
+	let dest_len = RATIO.estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, base, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+unsafe fn encode_bytes(bytes: &[u8], base: &Base45, mut dest: *mut u8) -> *mut u8 {
+	let mut chunks = bytes.chunks_exact(2);
+	for chunk in &mut chunks {
+		let value = (chunk[0] as usize) << 8 | chunk[1] as usize;
+		*dest = base.charset[value % 45];
+		*dest.add(1) = base.charset[value / 45 % 45];
+		*dest.add(2) = base.charset[value / (45 * 45)];
+		dest = dest.add(3);
+	}
+
+	if let &[byte] = chunks.remainder() {
+		let value = byte as usize;
+		*dest = base.charset[value % 45];
+		*dest.add(1) = base.charset[value / 45];
+		dest = dest.add(2);
+	}
+
+	return dest;
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], base: &Base45, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = RATIO.estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+// Decodes the digits of a group, least significant digit first
+#[inline]
+fn decode_group(group: &[u8], base: &Base45, position: usize) -> Result<u32, Error> {
+	let mut value = 0;
+	for (i, &byte) in group.iter().enumerate().rev() {
+		let digit = if byte < 128 { base.lut[byte as usize] } else { 255 };
+		if digit >= 45 {
+			return Err(Error::InvalidCharacter { offset: position + i, byte });
+		}
+		value = value * 45 + digit as u32;
+	}
+	Ok(value)
+}
+
+unsafe fn decode_string(string: &[u8], base: &Base45, mut dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut chunks = string.chunks_exact(3);
+	let mut position = 0;
+	for chunk in &mut chunks {
+		let value = decode_group(chunk, base, position)?;
+		if value > 0xffff {
+			return Err(Error::OutOfRange { offset: position });
+		}
+		*dest = (value >> 8) as u8;
+		*dest.add(1) = value as u8;
+		dest = dest.add(2);
+		position += 3;
+	}
+
+	let rest = chunks.remainder();
+	if !rest.is_empty() {
+		if rest.len() == 1 {
+			return Err(Error::IncorrectLength);
+		}
+		let value = decode_group(rest, base, position)?;
+		if value > 0xff {
+			return Err(Error::OutOfRange { offset: position });
+		}
+		*dest = value as u8;
+		dest = dest.add(1);
+	}
+
+	Ok(dest)
+}
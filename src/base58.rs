@@ -0,0 +1,314 @@
+use super::*;
+
+/// Base58 alphabet.
+///
+/// Base58 encodes the input as a single big number instead of in groups, every digit depends on all the input.
+/// There is no [`Encoding`] implementation, buffers are sized with [`Base58::estimate_encoded_len`] and [`Base58::estimate_decoded_len`].
+/// Encoding and decoding are quadratic in the length of the input and are meant for short strings such as keys, hashes and addresses.
+///
+/// Leading zero bytes are encoded as leading zero digits, the first character of the alphabet.
+#[derive(Clone, Debug)]
+pub struct Base58 {
+	charset: [u8; 58],
+	lut: [u8; 128],
+}
+
+impl Base58 {
+	/// Creates a new Base58 alphabet.
+	///
+	/// # Panics
+	///
+	/// Panics if the alphabet contains duplicate or non-ASCII characters.
+	pub const fn new(&charset: &[u8; 58]) -> Self {
+		let mut lut = [255; 128];
+		let mut i = 0;
+		while i < charset.len() {
+			if charset[i] as usize >= lut.len() {
+				panic!("non-ASCII character in Base58 charset");
+			}
+			if lut[charset[i] as usize] != 255 {
+				panic!("duplicate character in Base58 charset");
+			}
+			lut[charset[i] as usize] = i as u8;
+			i += 1;
+		}
+		Base58 { charset, lut }
+	}
+
+	/// Estimates the maximum length of the encoded string given the length of the input bytes.
+	///
+	/// The estimate includes a few bytes of scratch space required by the encoder.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		// log(256) / log(58) = 1.3657...
+		match (len / 100).checked_mul(138) {
+			Some(n) => match n.checked_add(len % 100 * 138 / 100 + 5) {
+				Some(n) => n,
+				None => ratio::panic_overflow(),
+			},
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the length of the input string.
+	///
+	/// Every leading zero digit decodes to a zero byte, the estimate includes a few bytes of scratch space required by the decoder.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		match len.checked_add(3) {
+			Some(n) => n,
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Encodes the input bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let encoded = basenc::Base58Btc.encode(b"hello world");
+	/// assert_eq!(encoded, "StV1DL6CwTryKyV");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode(&[bytes], self, std::string::String::new())
+	}
+
+	/// Decodes the input string.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let decoded = basenc::Base58Btc.decode("1112").unwrap();
+	/// assert_eq!(decoded, b"\0\0\0\x01");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode(string.as_bytes(), self, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// The buffer must have room for [`Base58::estimate_encoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::Base58;
+	///
+	/// let mut stack_buf = [0u8; Base58::estimate_encoded_len(4)];
+	/// let encoded = basenc::Base58Btc.encode_into(b"\x00\x00\x2a\x76", &mut stack_buf);
+	/// assert_eq!(encoded, "114ER");
+	/// ```
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(&[bytes], self, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`Base58::estimate_decoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::Base58;
+	///
+	/// let mut stack_buf = [0u8; Base58::estimate_decoded_len(5)];
+	/// let decoded = basenc::Base58Btc.decode_into("114ER", &mut stack_buf).unwrap();
+	/// assert_eq!(decoded, b"\x00\x00\x2a\x76");
+	/// ```
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode(string.as_bytes(), self, buffer)
+	}
+}
+
+mod check;
+pub use self::check::Base58Check;
+
+//----------------------------------------------------------------
+
+/// Base58 Bitcoin alphabet.
+///
+/// The alphabet is `1-9A-HJ-NP-Za-km-z`.
+#[allow(non_upper_case_globals)]
+pub static Base58Btc: Base58 = Base58::new(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+
+/// Base58 Ripple alphabet.
+///
+/// The alphabet is `rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz`.
+#[allow(non_upper_case_globals)]
+pub static Base58Ripple: Base58 = Base58::new(b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz");
+
+/// Base58 Flickr alphabet.
+///
+/// The alphabet is `1-9a-km-zA-HJ-NP-Z`.
+#[allow(non_upper_case_globals)]
+pub static Base58Flickr: Base58 = Base58::new(b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ");
+
+//----------------------------------------------------------------
+// Big numbers
+//
+// The number is accumulated in 32-bit limbs stored unaligned at the end of the destination buffer,
+// the most significant limb first. Each limb holds exactly four output digits or bytes which are
+// converted in place and finally moved to the front of the buffer.
+
+// Limb radix when encoding, four base58 digits
+const LIMB_RADIX: u64 = 58 * 58 * 58 * 58;
+
+#[inline]
+unsafe fn limb(end: *mut u8, i: usize) -> *mut u32 {
+	end.sub(4 * (i + 1)) as *mut u32
+}
+
+// Multiplies the number by `mul` and adds `carry`, returns the new number of limbs
+#[inline]
+unsafe fn mul_add(end: *mut u8, mut nlimbs: usize, mul: u64, mut carry: u64, radix: u64, max_limbs: usize) -> usize {
+	for i in 0..nlimbs {
+		let ptr = limb(end, i);
+		let x = ptr.read_unaligned() as u64 * mul + carry;
+		ptr.write_unaligned((x % radix) as u32);
+		carry = x / radix;
+	}
+	while carry != 0 {
+		assert!(nlimbs < max_limbs);
+		limb(end, nlimbs).write_unaligned((carry % radix) as u32);
+		carry /= radix;
+		nlimbs += 1;
+	}
+	nlimbs
+}
+
+// Moves the converted limbs to the front and prepends the leading zero digits
+#[inline]
+unsafe fn finish(dest: *mut u8, end: *mut u8, nlimbs: usize, zeros: usize, zero: u8) -> *mut u8 {
+	let start = end.sub(4 * nlimbs);
+	let skip = (0..cmp::min(4, 4 * nlimbs)).take_while(|&i| *start.add(i) == zero).count();
+	let len = 4 * nlimbs - skip;
+	ptr::copy(start.add(skip), dest.add(zeros), len);
+	ptr::write_bytes(dest, zero, zeros);
+	dest.add(zeros + len)
+}
+
+//----------------------------------------------------------------
+// Encoding
+
+// Encodes the concatenated parts
+#[inline(never)]
+fn encode<B: EncodeBuf>(parts: &[&[u8]], base: &Base58, mut buffer: B) -> B::Output {
+	let len = parts.iter().map(|part| part.len()).sum();
+	let dest_len = Base58::estimate_encoded_len(len);
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(parts, len, base, dest, dest_len);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+unsafe fn encode_bytes(parts: &[&[u8]], len: usize, base: &Base58, dest: *mut u8, dest_len: usize) -> *mut u8 {
+	let mut bytes = parts.iter().flat_map(|part| part.iter().copied()).peekable();
+	let mut zeros = 0;
+	while bytes.next_if_eq(&0).is_some() {
+		zeros += 1;
+	}
+
+	let end = dest.add(dest_len);
+	let max_limbs = dest_len / 4;
+	let mut nlimbs = 0;
+
+	// Feed the bytes in groups of four, the first group takes the remainder
+	let mut remaining = len - zeros;
+	let mut group_len = (remaining + 3) % 4 + 1;
+	while remaining > 0 {
+		let value = bytes.by_ref().take(group_len).fold(0u64, |acc, byte| acc << 8 | byte as u64);
+		nlimbs = mul_add(end, nlimbs, 1 << (8 * group_len), value, LIMB_RADIX, max_limbs);
+		remaining -= group_len;
+		group_len = 4;
+	}
+
+	// Convert every limb into four digits
+	for i in 0..nlimbs {
+		let ptr = limb(end, i);
+		let mut value = ptr.read_unaligned();
+		let mut digits = [0u8; 4];
+		for digit in digits.iter_mut().rev() {
+			*digit = base.charset[(value % 58) as usize];
+			value /= 58;
+		}
+		(ptr as *mut [u8; 4]).write_unaligned(digits);
+	}
+
+	finish(dest, end, nlimbs, zeros, base.charset[0])
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], base: &Base58, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = Base58::estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, dest, dest_len)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+#[inline]
+fn lookup(string: &[u8], i: usize, lut: &[u8; 128]) -> Result<u8, Error> {
+	let byte = string[i];
+	if byte as usize >= lut.len() {
+		return Err(Error::InvalidCharacter { offset: i, byte });
+	}
+	let v = lut[byte as usize];
+	if v >= 58 {
+		return Err(Error::InvalidCharacter { offset: i, byte });
+	}
+	Ok(v)
+}
+
+unsafe fn decode_string(string: &[u8], base: &Base58, dest: *mut u8, dest_len: usize) -> Result<*mut u8, Error> {
+	let zeros = string.iter().take_while(|&&chr| chr == base.charset[0]).count();
+
+	let end = dest.add(dest_len);
+	let max_limbs = dest_len / 4;
+	let mut nlimbs = 0;
+
+	// Feed the digits in groups of five, the first group takes the remainder
+	let mut i = zeros;
+	let mut group_len = (string.len() - zeros + 4) % 5 + 1;
+	while i < string.len() {
+		let mut value = 0;
+		let mut mul = 1;
+		for j in i..i + group_len {
+			value = value * 58 + lookup(string, j, &base.lut)? as u64;
+			mul *= 58;
+		}
+		nlimbs = mul_add(end, nlimbs, mul, value, 1 << 32, max_limbs);
+		i += group_len;
+		group_len = 5;
+	}
+
+	// Convert every limb into four bytes
+	for i in 0..nlimbs {
+		let ptr = limb(end, i);
+		(ptr as *mut [u8; 4]).write_unaligned(ptr.read_unaligned().to_be_bytes());
+	}
+
+	Ok(finish(dest, end, nlimbs, zeros, 0))
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+/// Base58Check encoding.
+///
+/// A version byte is prepended to the payload and the first four bytes of the double SHA-256 of both are appended as checksum.
+/// Used by Bitcoin addresses and WIF private keys.
+///
+/// # Examples
+///
+/// ```
+/// let hash160 = b"\x62\xe9\x07\xb1\x5c\xbf\x27\xd5\x42\x53\x99\xeb\xf6\xf0\xfb\x50\xeb\xb8\x8f\x18";
+///
+/// let address = basenc::Base58Btc.check().encode(0x00, hash160);
+/// assert_eq!(address, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+///
+/// let (version, payload) = basenc::Base58Btc.check().decode(&address).unwrap();
+/// assert_eq!(version, 0x00);
+/// assert_eq!(payload, hash160);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Base58Check<'a> {
+	base: &'a Base58,
+}
+
+impl Base58 {
+	/// With version byte and checksum.
+	#[inline]
+	pub const fn check(&self) -> Base58Check<'_> {
+		Base58Check { base: self }
+	}
+}
+
+impl Base58Check<'_> {
+	/// Estimates the maximum length of the encoded string given the length of the payload.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		match len.checked_add(5) {
+			Some(len) => Base58::estimate_encoded_len(len),
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the length of the input string.
+	///
+	/// The decoder requires room for the version byte and checksum, the payload is shorter.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		Base58::estimate_decoded_len(len)
+	}
+
+	/// Encodes the version byte and payload.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, version: u8, payload: &[u8]) -> std::string::String {
+		self.encode_into(version, payload, std::string::String::new())
+	}
+
+	/// Decodes the input string into the version byte and payload.
+	///
+	/// Returns [`Error::ChecksumMismatch`] if the checksum does not match and [`Error::IncorrectLength`] if the input is too short to contain a version byte and checksum.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<(u8, std::vec::Vec<u8>), Error> {
+		self.decode_into(string, std::vec::Vec::new())
+	}
+
+	/// Encodes the version byte and payload into a buffer.
+	///
+	/// The buffer must have room for [`Base58Check::estimate_encoded_len`] bytes.
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, version: u8, payload: &[u8], buffer: B) -> B::Output {
+		let digest = sha256::sha256d(&[&[version], payload]);
+		encode(&[&[version], payload, &digest[..4]], self.base, buffer)
+	}
+
+	/// Decodes the input string into the version byte and a buffer receiving the payload.
+	///
+	/// The buffer must have room for [`Base58Check::estimate_decoded_len`] bytes.
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<(u8, B::Output), Error> {
+		decode_check(string.as_bytes(), self.base, buffer)
+	}
+}
+
+#[inline(never)]
+fn decode_check<B: DecodeBuf>(string: &[u8], base: &Base58, mut buffer: B) -> Result<(u8, B::Output), Error> {
+	let dest_len = Base58::estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, dest, dest_len)?;
+		let len = end.offset_from(dest) as usize;
+		if len < 5 {
+			return Err(Error::IncorrectLength);
+		}
+
+		let (data, checksum) = slice::from_raw_parts(dest, len).split_at(len - 4);
+		if sha256::sha256d(&[data])[..4] != *checksum {
+			return Err(Error::ChecksumMismatch);
+		}
+
+		// Strip the version byte
+		let version = *dest;
+		ptr::copy(dest.add(1), dest, len - 5);
+		Ok((version, buffer.commit(len - 5)))
+	}
+}
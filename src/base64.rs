@@ -84,7 +84,7 @@ impl Base64 {
 
 	/// With explicit padding policy.
 	pub const fn pad(&self, pad: Padding) -> WithPad<'_, Self> {
-		WithPad { encoding: self, pad }
+		WithPad::new(self, pad)
 	}
 }
 
@@ -98,7 +98,7 @@ impl Encoding for Base64 {
 
 	#[inline]
 	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
-		decode(string, self, Padding::Optional, buffer)
+		decode(string, self, Padding::Optional, Mode::Normal, buffer)
 	}
 }
 
@@ -112,7 +112,7 @@ impl Encoding for WithPad<'_, Base64> {
 
 	#[inline]
 	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
-		decode(string, self.encoding, self.pad, buffer)
+		decode(string, self.encoding, self.pad, self.mode, buffer)
 	}
 }
 
@@ -145,6 +145,7 @@ impl_encoding!(Base64,
 		"```",
 	],
 );
+impl_whitespace!(Base64);
 
 //----------------------------------------------------------------
 
@@ -183,13 +184,18 @@ fn encode<B: EncodeBuf>(bytes: &[u8], base: &Base64, pad: Padding, mut buffer: B
 mod decode;
 
 #[inline(never)]
-fn decode<B: DecodeBuf>(string: &[u8], base: &Base64, pad: Padding, mut buffer: B) -> Result<B::Output, Error> {
+fn decode<B: DecodeBuf>(string: &[u8], base: &Base64, pad: Padding, mode: Mode, mut buffer: B) -> Result<B::Output, Error> {
 	let dest_len = RATIO.estimate_decoded_len(string.len());
 
 	unsafe {
 		let dest = buffer.allocate(dest_len);
-		let end = decode::decode_fn()(string, base, pad, dest)?;
+		let end = decode::decode_fn()(string, base, pad, mode, dest)?;
 		let len = end.offset_from(dest) as usize;
 		Ok(buffer.commit(len))
 	}
 }
+
+//----------------------------------------------------------------
+// Constant-time
+
+mod ct;
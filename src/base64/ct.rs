@@ -0,0 +1,226 @@
+use super::*;
+use crate::ct::{eq, lt, range, ConstantTime};
+
+impl ConstantTime<'_, Base64> {
+	/// With explicit padding policy.
+	pub const fn pad(&self, pad: Padding) -> WithPad<'_, Self> {
+		WithPad::new(self, pad)
+	}
+}
+
+impl Encoding for ConstantTime<'_, Base64> {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self.encoding, Padding::Optional, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self.encoding, Padding::Optional, Mode::Normal, buffer)
+	}
+}
+
+impl Encoding for WithPad<'_, ConstantTime<'_, Base64>> {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self.encoding.encoding, self.pad, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self.encoding.encoding, self.pad, self.mode, buffer)
+	}
+}
+
+impl_encoding!(ConstantTime<'_, Base64>);
+impl_whitespace!(ConstantTime<'_, Base64>);
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline]
+fn encode_value(value: u32, base: &Base64) -> u8 {
+	let upper = lt(value, 26);
+	let lower = !upper & lt(value, 52);
+	let digit = !lt(value, 52) & lt(value, 62);
+	let chr =
+		upper & (value + b'A' as u32) |
+		lower & (value + (b'a' - 26) as u32) |
+		digit & value.wrapping_sub((52 - b'0') as u32) |
+		eq(value, 62) & base.charset[62] as u32 |
+		eq(value, 63) & base.charset[63] as u32;
+	chr as u8
+}
+
+unsafe fn encode_bytes(bytes: &[u8], base: &Base64, pad: Padding, mut dest: *mut u8) -> *mut u8 {
+	for chunk in bytes.chunks(3) {
+		// aaaaaabb bbbbcccc ccdddddd
+		let mut word = 0u32;
+		for (i, &byte) in chunk.iter().enumerate() {
+			word |= (byte as u32) << (16 - 8 * i);
+		}
+
+		let len = chunk.len() + 1;
+		for i in 0..len {
+			*dest.add(i) = encode_value(word >> (18 - 6 * i) & 0x3f, base);
+		}
+		dest = dest.add(len);
+
+		if matches!(pad, Padding::Strict) {
+			for _ in len..4 {
+				*dest = PAD_CHAR;
+				dest = dest.add(1);
+			}
+		}
+	}
+
+	return dest;
+}
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], base: &Base64, pad: Padding, mut buffer: B) -> B::Output {
+	let dest_len = RATIO.estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, base, pad, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+/// Returns the value and a mask with all bits set if the character is valid.
+#[inline]
+fn decode_char(chr: u8, base: &Base64) -> (u32, u32) {
+	let chr = chr as u32;
+	let upper = range(chr, b'A', b'Z');
+	let lower = range(chr, b'a', b'z');
+	let digit = range(chr, b'0', b'9');
+	let char62 = eq(chr, base.charset[62] as u32);
+	let char63 = eq(chr, base.charset[63] as u32);
+	let value =
+		upper & chr.wrapping_sub(b'A' as u32) |
+		lower & chr.wrapping_sub((b'a' - 26) as u32) |
+		digit & (chr + (52 - b'0') as u32) |
+		char62 & 62 |
+		char63 & 63;
+	(value, upper | lower | digit | char62 | char63)
+}
+
+// Decodes 2 to 4 characters into 1 to 3 bytes
+#[inline]
+unsafe fn decode_chunk(chunk: &[u8], base: &Base64, mode: Mode, invalid: &mut u32, dest: *mut u8) -> *mut u8 {
+	let mut word = 0u32;
+	for (i, &chr) in chunk.iter().enumerate() {
+		let (value, valid) = decode_char(chr, base);
+		word |= value << (18 - 6 * i);
+		*invalid |= !valid;
+	}
+
+	let len = chunk.len() - 1;
+	if !matches!(mode, Mode::Lenient) {
+		// The trailing bits must be zero
+		*invalid |= word & ((1 << (24 - 8 * len)) - 1);
+	}
+
+	for i in 0..len {
+		*dest.add(i) = (word >> (16 - 8 * i)) as u8;
+	}
+	dest.add(len)
+}
+
+unsafe fn decode_string(string: &[u8], base: &Base64, pad: Padding, mode: Mode, dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut invalid = 0;
+	let mut rest = string;
+	let mut end = dest;
+
+	while rest.len() >= 4 {
+		let chunk = &rest[..4];
+
+		let padded = !matches!(pad, Padding::None) && chunk[3] == PAD_CHAR;
+		let len = if !padded { 4 } else if chunk[2] == PAD_CHAR { 2 } else { 3 };
+		end = decode_chunk(&chunk[..len], base, mode, &mut invalid, end);
+
+		// Padding is only accepted in the final chunk
+		if padded && matches!(mode, Mode::Strict) && rest.len() > 4 {
+			invalid = !0;
+		}
+
+		rest = &rest[4..];
+	}
+
+	if rest.len() != 0 {
+		if matches!(pad, Padding::Strict) || rest.len() == 1 {
+			invalid = !0;
+		}
+		else {
+			end = decode_chunk(rest, base, mode, &mut invalid, end);
+		}
+	}
+
+	if invalid != 0 {
+		return Err(locate(string, base, pad, mode));
+	}
+
+	Ok(end)
+}
+
+// Locates the error found above in the same order as the regular decoder, the input is not secret once rejected
+#[cold]
+fn locate(string: &[u8], base: &Base64, pad: Padding, mode: Mode) -> Error {
+	let mut offset = 0;
+	while string.len() - offset >= 4 {
+		let chunk = &string[offset..offset + 4];
+
+		let padded = !matches!(pad, Padding::None) && chunk[3] == PAD_CHAR;
+		let len = if !padded { 4 } else if chunk[2] == PAD_CHAR { 2 } else { 3 };
+		if let Some(err) = locate_chunk(&chunk[..len], base, mode) {
+			return err.offset_by(offset);
+		}
+
+		if padded && matches!(mode, Mode::Strict) && string.len() - offset > 4 {
+			return Error::InvalidCharacter { offset: offset + len, byte: PAD_CHAR };
+		}
+
+		offset += 4;
+	}
+
+	let rest = &string[offset..];
+	if !matches!(pad, Padding::Strict) && rest.len() >= 2 {
+		if let Some(err) = locate_chunk(rest, base, mode) {
+			return err.offset_by(offset);
+		}
+	}
+
+	// All characters are valid, the remaining characters are too few or not padded
+	Error::IncorrectLength
+}
+
+fn locate_chunk(chunk: &[u8], base: &Base64, mode: Mode) -> Option<Error> {
+	if let Some(offset) = chunk.iter().position(|&chr| decode_char(chr, base).1 == 0) {
+		return Some(Error::InvalidCharacter { offset, byte: chunk[offset] });
+	}
+	let mut invalid = 0;
+	let mut scratch = Scrub([0u8; 3]);
+	unsafe { decode_chunk(chunk, base, mode, &mut invalid, scratch.as_mut_ptr()) };
+	if invalid != 0 { Some(Error::NonCanonical { offset: 0 }) } else { None }
+}
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], base: &Base64, pad: Padding, mode: Mode, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = RATIO.estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, pad, mode, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
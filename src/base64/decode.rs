@@ -2,8 +2,10 @@ use super::*;
 
 mod scalar;
 
+type DecodeFn = unsafe fn(string: &[u8], base: &Base64, pad: Padding, mode: Mode, dest: *mut u8) -> Result<*mut u8, crate::Error>;
+
 impl_arch_decode! {
-	unsafe fn(string: &[u8], base: &Base64, pad: Padding, dest: *mut u8) -> Result<*mut u8, crate::Error>;
+	DecodeFn;
 
 	(any(target_arch = "x86_64", target_arch = "x86")) => {
 		ssse3: "ssse3" is_x86_feature_detected!("ssse3");
@@ -1,22 +1,24 @@
 use super::*;
 
-fn lookup(byte: u8, lut: &[u8; 128]) -> Result<u8, crate::Error> {
+#[inline]
+fn lookup(chunk: &[u8], i: usize, lut: &[u8; 128]) -> Result<u8, crate::Error> {
+	let byte = chunk[i];
 	if byte as usize >= lut.len() {
-		return Err(crate::Error::InvalidCharacter);
+		return Err(crate::Error::InvalidCharacter { offset: i, byte });
 	}
 	let v = lut[byte as usize];
 	if v >= 64 {
-		return Err(crate::Error::InvalidCharacter);
+		return Err(crate::Error::InvalidCharacter { offset: i, byte });
 	}
 	Ok(v)
 }
 
 // aaaaaabb bbbbcccc ccdddddd
 unsafe fn decode_4bytes(chunk: &[u8; 4], base: &Base64, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
-	let c = lookup(chunk[2], &base.lut)?;
-	let d = lookup(chunk[3], &base.lut)?;
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
+	let c = lookup(chunk, 2, &base.lut)?;
+	let d = lookup(chunk, 3, &base.lut)?;
 
 	*dest.add(0) = a << 2 | b >> 4;
 	*dest.add(1) = b << 4 | c >> 2;
@@ -26,13 +28,13 @@ unsafe fn decode_4bytes(chunk: &[u8; 4], base: &Base64, dest: *mut u8) -> Result
 }
 
 // aaaaaabb bbbbcccc 00------
-unsafe fn decode_3bytes(chunk: &[u8; 3], base: &Base64, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
-	let c = lookup(chunk[2], &base.lut)?;
+unsafe fn decode_3bytes(chunk: &[u8; 3], base: &Base64, mode: Mode, dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
+	let c = lookup(chunk, 2, &base.lut)?;
 
-	if c & 0x3 != 0 {
-		return Err(crate::Error::NonCanonical);
+	if !matches!(mode, Mode::Lenient) && c & 0x3 != 0 {
+		return Err(crate::Error::NonCanonical { offset: 0 });
 	}
 
 	*dest.add(0) = a << 2 | b >> 4;
@@ -42,12 +44,12 @@ unsafe fn decode_3bytes(chunk: &[u8; 3], base: &Base64, dest: *mut u8) -> Result
 }
 
 // aaaaaabb 0000----
-unsafe fn decode_2bytes(chunk: &[u8; 2], base: &Base64, dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	let a = lookup(chunk[0], &base.lut)?;
-	let b = lookup(chunk[1], &base.lut)?;
+unsafe fn decode_2bytes(chunk: &[u8; 2], base: &Base64, mode: Mode, dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let a = lookup(chunk, 0, &base.lut)?;
+	let b = lookup(chunk, 1, &base.lut)?;
 
-	if b & 0xf != 0 {
-		return Err(crate::Error::NonCanonical);
+	if !matches!(mode, Mode::Lenient) && b & 0xf != 0 {
+		return Err(crate::Error::NonCanonical { offset: 0 });
 	}
 
 	*dest.add(0) = a << 2 | b >> 4;
@@ -55,20 +57,30 @@ unsafe fn decode_2bytes(chunk: &[u8; 2], base: &Base64, dest: *mut u8) -> Result
 	Ok(dest.add(1))
 }
 
-pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mode: Mode, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let input_len = string.len();
+
 	while string.len() >= 4 {
 		let chunk = &*(string.as_ptr() as *const [u8; 4]);
 
-		if !matches!(pad, Padding::None) && chunk[3] == PAD_CHAR {
+		let padded = !matches!(pad, Padding::None) && chunk[3] == PAD_CHAR;
+		let result = if padded {
 			if chunk[2] == PAD_CHAR {
-				dest = decode_2bytes(&*(chunk as *const _ as *const [u8; 2]), base, dest)?;
+				decode_2bytes(&*(chunk as *const _ as *const [u8; 2]), base, mode, dest)
 			}
 			else {
-				dest = decode_3bytes(&*(chunk as *const _ as *const [u8; 3]), base, dest)?;
+				decode_3bytes(&*(chunk as *const _ as *const [u8; 3]), base, mode, dest)
 			}
 		}
 		else {
-			dest = decode_4bytes(chunk, base, dest)?;
+			decode_4bytes(chunk, base, dest)
+		};
+		dest = result.map_err(|err| err.offset_by(input_len - string.len()))?;
+
+		// Padding is only accepted in the final chunk
+		if padded && matches!(mode, Mode::Strict) && string.len() > 4 {
+			let offset = input_len - string.len() + if chunk[2] == PAD_CHAR { 2 } else { 3 };
+			return Err(crate::Error::InvalidCharacter { offset, byte: PAD_CHAR });
 		}
 
 		string = &string[4..];
@@ -80,11 +92,12 @@ pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mut dest: *
 		}
 
 		// Decode remaining bytes
-		dest = match string.len() {
-			3 => decode_3bytes(&*(string.as_ptr() as *const [u8; 3]), base, dest)?,
-			2 => decode_2bytes(&*(string.as_ptr() as *const [u8; 2]), base, dest)?,
+		let result = match string.len() {
+			3 => decode_3bytes(&*(string.as_ptr() as *const [u8; 3]), base, mode, dest),
+			2 => decode_2bytes(&*(string.as_ptr() as *const [u8; 2]), base, mode, dest),
 			_ => return Err(crate::Error::IncorrectLength),
 		};
+		dest = result.map_err(|err| err.offset_by(input_len - string.len()))?;
 	}
 
 	Ok(dest)
@@ -8,18 +8,26 @@ use core::arch::x86_64::*;
 use super::*;
 
 #[target_feature(enable = "sse2")]
-pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mode: Mode, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
 	if string.len() == 0 {
 		return Ok(dest);
 	}
 
+	let input_len = string.len();
+
 	while string.len() >= 16 {
 		let block = _mm_loadu_si128(string.as_ptr() as *const __m128i);
 
 		let Ok(values) = lookup(block, base)
 		else {
+			// Padding must be in the final chunk, let the scalar code path see the rest of the input
+			if matches!(mode, Mode::Strict) {
+				break;
+			}
+
 			// Handle errors and padding with the scalar code path
-			dest = scalar::decode(&string[..16], base, pad, dest)?;
+			dest = scalar::decode(&string[..16], base, pad, mode, dest)
+				.map_err(|err| err.offset_by(input_len - string.len()))?;
 			string = &string[16..];
 			continue;
 		};
@@ -33,14 +41,15 @@ pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mut dest: *
 		string = &string[16..];
 	}
 
-	scalar::decode(string, base, pad, dest)
+	scalar::decode(string, base, pad, mode, dest)
+		.map_err(|err| err.offset_by(input_len - string.len()))
 }
 
 //----------------------------------------------------------------
 
 #[inline]
 #[target_feature(enable = "sse2")]
-unsafe fn lookup(input: __m128i, base: &Base64) -> Result<__m128i, crate::Error> {
+unsafe fn lookup(input: __m128i, base: &Base64) -> Result<__m128i, ()> {
 	#![allow(non_snake_case)]
 
 	// shift for range 'A' - 'Z'
@@ -80,7 +89,7 @@ unsafe fn lookup(input: __m128i, base: &Base64) -> Result<__m128i, crate::Error>
 	// check for errors
 	let mask = _mm_movemask_epi8(_mm_cmpeq_epi8(shift, _mm_setzero_si128()));
 	if mask != 0 {
-		return Err(crate::Error::InvalidCharacter);
+		return Err(());
 	}
 
 	Ok(_mm_add_epi8(input, shift))
@@ -8,18 +8,26 @@ use core::arch::x86_64::*;
 use super::*;
 
 #[target_feature(enable = "ssse3")]
-pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mode: Mode, mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
 	if string.len() == 0 {
 		return Ok(dest);
 	}
 
+	let input_len = string.len();
+
 	while string.len() >= 16 {
 		let block = _mm_loadu_si128(string.as_ptr() as *const __m128i);
 
 		let Ok(values) = lookup(block, base)
 		else {
+			// Padding must be in the final chunk, let the scalar code path see the rest of the input
+			if matches!(mode, Mode::Strict) {
+				break;
+			}
+
 			// Handle errors and padding with the scalar code path
-			dest = scalar::decode(&string[..16], base, pad, dest)?;
+			dest = scalar::decode(&string[..16], base, pad, mode, dest)
+				.map_err(|err| err.offset_by(input_len - string.len()))?;
 			string = &string[16..];
 			continue;
 		};
@@ -34,14 +42,15 @@ pub unsafe fn decode(mut string: &[u8], base: &Base64, pad: Padding, mut dest: *
 		continue;
 	}
 
-	scalar::decode(string, base, pad, dest)
+	scalar::decode(string, base, pad, mode, dest)
+		.map_err(|err| err.offset_by(input_len - string.len()))
 }
 
 //----------------------------------------------------------------
 
 #[inline]
 #[target_feature(enable = "ssse3")]
-unsafe fn lookup(input: __m128i, base: &Base64) -> Result<__m128i, crate::Error> {
+unsafe fn lookup(input: __m128i, base: &Base64) -> Result<__m128i, ()> {
 	let higher_nibble = _mm_and_si128(_mm_srli_epi32(input, 4), _mm_set1_epi8(0x0f));
 
 	let linv = 1;
@@ -79,7 +88,7 @@ unsafe fn lookup(input: __m128i, base: &Base64) -> Result<__m128i, crate::Error>
 
 	let outside = _mm_andnot_si128(mask_eq, _mm_or_si128(mask_below, mask_above));
 	if _mm_movemask_epi8(outside) != 0 {
-		return Err(crate::Error::InvalidCharacter);
+		return Err(());
 	}
 
 	let shift_bound = _mm_shuffle_epi8(shuft_lut, higher_nibble);
@@ -0,0 +1,202 @@
+use super::*;
+
+const RATIO: Ratio = Ratio { decoded: 4, encoded: 5 };
+
+/// Base85 alphabet.
+///
+/// Every group of four bytes is encoded as five characters, most significant digit first.
+/// A final partial group of `n` bytes is encoded as `n + 1` characters.
+///
+/// See [`Z85`] for the ZeroMQ variant and [`Ascii85`] for the Adobe variant.
+#[derive(Clone, Debug)]
+pub struct Base85 {
+	charset: [u8; 85],
+	lut: [u8; 128],
+}
+
+impl Base85 {
+	/// Creates a new Base85 alphabet.
+	///
+	/// # Panics
+	///
+	/// Panics if the alphabet contains duplicate or non-ASCII characters.
+	pub const fn new(&charset: &[u8; 85]) -> Self {
+		let mut lut = [255; 128];
+		let mut i = 0;
+		while i < charset.len() {
+			if charset[i] as usize >= lut.len() {
+				panic!("non-ASCII character in Base85 charset");
+			}
+			if lut[charset[i] as usize] != 255 {
+				panic!("duplicate character in Base85 charset");
+			}
+			lut[charset[i] as usize] = i as u8;
+			i += 1;
+		}
+		Base85 { charset, lut }
+	}
+}
+
+impl Encoding for Base85 {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self, buffer)
+	}
+}
+
+impl_encoding!(Base85,
+	encode: [
+		"```",
+		"let encoded = basenc::Base85Rfc1924.encode(b\"hello world\");",
+		"assert_eq!(encoded, \"Xk~0{Zy<MXa%^M\");",
+		"```",
+	],
+	decode: [
+		"```",
+		"let decoded = basenc::Base85Rfc1924.decode(\"Xk~0{Zy<MXa%^M\").unwrap();",
+		"assert_eq!(decoded, b\"hello world\");",
+		"```",
+	],
+	encode_into: [
+		"```",
+		"let mut stack_buf = [0u8; 16];",
+		"let encoded = basenc::Base85Rfc1924.encode_into(b\"hello world\", &mut stack_buf);",
+		"assert_eq!(encoded, \"Xk~0{Zy<MXa%^M\");",
+		"```",
+	],
+	decode_into: [
+		"```",
+		"let decoded = basenc::Base85Rfc1924.decode_into(\"Xk~0{Zy<MXa%^M\", Vec::new()).unwrap();",
+		"assert_eq!(decoded, b\"hello world\");",
+		"```",
+	],
+);
+impl_whitespace!(Base85);
+
+mod git;
+pub use self::git::BinaryPatch;
+
+//----------------------------------------------------------------
+
+/// Base85 RFC 1924 alphabet.
+///
+/// The alphabet is ``0-9A-Za-z!#$%&()*+-;<=>?@^_`{|}~``.
+/// Git binary patches use the same alphabet, see [`Base85::binary_patch`] for the line framing.
+#[allow(non_upper_case_globals)]
+pub static Base85Rfc1924: Base85 = Base85::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~");
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline(never)]
+pub(crate) fn encode<B: EncodeBuf>(bytes: &[u8], base: &Base85, mut buffer: B) -> B::Output {
+	let dest_len = RATIO.estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, base, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+#[inline]
+fn encode_word(mut word: u32, base: &Base85) -> [u8; 5] {
+	let mut chars = [0u8; 5];
+	for chr in chars.iter_mut().rev() {
+		*chr = base.charset[(word % 85) as usize];
+		word /= 85;
+	}
+	chars
+}
+
+unsafe fn encode_bytes(bytes: &[u8], base: &Base85, mut dest: *mut u8) -> *mut u8 {
+	let mut chunks = bytes.chunks_exact(4);
+	for chunk in &mut chunks {
+		let word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+		(dest as *mut [u8; 5]).write_unaligned(encode_word(word, base));
+		dest = dest.add(5);
+	}
+
+	// Pad the final group with zeros and drop the extra characters
+	let rest = chunks.remainder();
+	if !rest.is_empty() {
+		let mut group = [0u8; 4];
+		group[..rest.len()].copy_from_slice(rest);
+		let chars = encode_word(u32::from_be_bytes(group), base);
+		ptr::copy_nonoverlapping(chars.as_ptr(), dest, rest.len() + 1);
+		dest = dest.add(rest.len() + 1);
+	}
+
+	return dest;
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+#[inline(never)]
+pub(crate) fn decode<B: DecodeBuf>(string: &[u8], base: &Base85, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = RATIO.estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+// Decodes a group of up to 5 characters, missing characters are taken as the highest digit
+#[inline]
+fn decode_word(group: &[u8], base: &Base85, position: usize) -> Result<u32, Error> {
+	let mut word = 0u32;
+	for i in 0..5 {
+		let (digit, error) = match group.get(i) {
+			Some(&byte) => {
+				let digit = if byte < 128 { base.lut[byte as usize] } else { 255 };
+				let error = Error::InvalidCharacter { offset: position + i, byte };
+				if digit >= 85 {
+					return Err(error);
+				}
+				(digit, error)
+			},
+			None => (84, Error::IncorrectLength),
+		};
+		// Groups above 2^32 - 1 cannot be produced by the encoder
+		word = match word.checked_mul(85).and_then(|word| word.checked_add(digit as u32)) {
+			Some(word) => word,
+			None => return Err(error),
+		};
+	}
+	Ok(word)
+}
+
+unsafe fn decode_string(string: &[u8], base: &Base85, mut dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut chunks = string.chunks_exact(5);
+	let mut position = 0;
+	for chunk in &mut chunks {
+		let word = decode_word(chunk, base, position)?;
+		(dest as *mut [u8; 4]).write_unaligned(word.to_be_bytes());
+		dest = dest.add(4);
+		position += 5;
+	}
+
+	let rest = chunks.remainder();
+	if !rest.is_empty() {
+		if rest.len() == 1 {
+			return Err(Error::IncorrectLength);
+		}
+		let word = decode_word(rest, base, position)?;
+		ptr::copy_nonoverlapping(word.to_be_bytes().as_ptr(), dest, rest.len() - 1);
+		dest = dest.add(rest.len() - 1);
+	}
+
+	Ok(dest)
+}
@@ -0,0 +1,172 @@
+use super::*;
+
+// Maximum number of bytes per line
+const LINE_LEN: usize = 52;
+
+/// Line framing of git binary patches.
+///
+/// Every line encodes up to 52 bytes and starts with a length character, `A-Z` for 1 to 26 and `a-z` for 27 to 52 bytes.
+/// The bytes are zero padded to a multiple of four and encoded in full groups, each line ends with a line feed.
+///
+/// Only the line framing is handled, the data following the `literal` and `delta` headers of a `GIT binary patch` is zlib compressed.
+///
+/// # Examples
+///
+/// ```
+/// let encoded = basenc::Base85Rfc1924.binary_patch().encode(b"hello world");
+/// assert_eq!(encoded, "KXk~0{Zy<MXa%^M(\n");
+///
+/// let decoded = basenc::Base85Rfc1924.binary_patch().decode(&encoded).unwrap();
+/// assert_eq!(decoded, b"hello world");
+/// ```
+#[derive(Clone, Debug)]
+pub struct BinaryPatch<'a> {
+	base: &'a Base85,
+}
+
+impl Base85 {
+	/// With git binary patch line framing.
+	#[inline]
+	pub const fn binary_patch(&self) -> BinaryPatch<'_> {
+		BinaryPatch { base: self }
+	}
+}
+
+impl BinaryPatch<'_> {
+	/// Estimates the maximum length of the encoded string given the length of the input bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		let full_lines = len / LINE_LEN;
+		let rest = len % LINE_LEN;
+		let last_line = if rest > 0 { RATIO.estimate_encoded_len(rest.div_ceil(4) * 4) + 2 } else { 0 };
+		match full_lines.checked_mul(RATIO.estimate_encoded_len(LINE_LEN) + 2) {
+			Some(len) => match len.checked_add(last_line) {
+				Some(len) => len,
+				None => ratio::panic_overflow(),
+			},
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the length of the input string.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		RATIO.estimate_decoded_len(len)
+	}
+
+	/// Encodes the input bytes.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode_lines(bytes, self.base, std::string::String::new())
+	}
+
+	/// Decodes the input lines.
+	///
+	/// Accepts `\n` and `\r\n` line endings, trailing empty lines are ignored.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode_lines(string.as_bytes(), self.base, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// The buffer must have room for [`BinaryPatch::estimate_encoded_len`] bytes.
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode_lines(bytes, self.base, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`BinaryPatch::estimate_decoded_len`] bytes.
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode_lines(string.as_bytes(), self.base, buffer)
+	}
+}
+
+//----------------------------------------------------------------
+
+#[inline(never)]
+fn encode_lines<B: EncodeBuf>(bytes: &[u8], base: &Base85, mut buffer: B) -> B::Output {
+	let dest_len = BinaryPatch::estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let mut end = dest;
+		for line in bytes.chunks(LINE_LEN) {
+			*end = if line.len() <= 26 { b'A' + line.len() as u8 - 1 } else { b'a' + line.len() as u8 - 27 };
+			end = end.add(1);
+
+			let mut group = [0u8; 4];
+			for chunk in line.chunks(4) {
+				group[..chunk.len()].copy_from_slice(chunk);
+				group[chunk.len()..].fill(0);
+				(end as *mut [u8; 5]).write_unaligned(encode_word(u32::from_be_bytes(group), base));
+				end = end.add(5);
+			}
+
+			*end = b'\n';
+			end = end.add(1);
+		}
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+#[inline(never)]
+fn decode_lines<B: DecodeBuf>(string: &[u8], base: &Base85, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = BinaryPatch::estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let mut end = dest;
+		let mut position = 0;
+		let mut trailing = false;
+
+		for line in string.split_inclusive(|&chr| chr == b'\n') {
+			let offset = position;
+			position += line.len();
+			let line = line.strip_suffix(b"\n").unwrap_or(line);
+			let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+			if line.is_empty() {
+				trailing = true;
+				continue;
+			}
+			if trailing {
+				// Data after an empty line
+				return Err(Error::InvalidCharacter { offset, byte: line[0] });
+			}
+
+			let len = match line[0] {
+				byte @ b'A'..=b'Z' => (byte - b'A') as usize + 1,
+				byte @ b'a'..=b'z' => (byte - b'a') as usize + 27,
+				byte => return Err(Error::InvalidCharacter { offset, byte }),
+			};
+
+			let groups = &line[1..];
+			if groups.len() != len.div_ceil(4) * 5 {
+				return Err(Error::IncorrectLength);
+			}
+
+			let mut remaining = len;
+			for (i, group) in groups.chunks_exact(5).enumerate() {
+				let word = decode_word(group, base, offset + 1 + i * 5)?;
+				let n = cmp::min(remaining, 4);
+				ptr::copy_nonoverlapping(word.to_be_bytes().as_ptr(), end, n);
+				end = end.add(n);
+				remaining -= n;
+			}
+		}
+
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
@@ -0,0 +1,466 @@
+use super::*;
+
+const RATIO: Ratio = Ratio { decoded: 13, encoded: 16 };
+
+/// basE91 alphabet.
+///
+/// basE91 encodes the input as a stream of 13 or 14 bit values, each written as two characters.
+/// The encoded output is at most 23% larger than the input, compared to 33% for base64.
+///
+/// The [`RATIO`](Encoding::RATIO) of 13 bytes to 16 characters is an upper bound for encoding.
+/// Two characters may decode to 14 bits, decoding sizes its buffer with [`Base91::estimate_decoded_len`] instead, also when decoding incrementally.
+///
+/// The stream is not split in chunks, the [`incremental`] encoder and decoder keep the pending bits between updates.
+/// See [`Base91Encoder`] and [`Base91Decoder`] to encode and decode in chunks without them.
+#[derive(Clone, Debug)]
+pub struct Base91 {
+	charset: [u8; 91],
+	lut: [u8; 128],
+}
+
+impl Encoding for Base91 {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, self, buffer)
+	}
+
+	fn encode_update<B: EncodeBuf>(&self, state: &mut incremental::State, bytes: &[u8], buffer: B) -> B::Output {
+		let mut queue = Scrub(Queue { bits: *state.bits, nbits: state.nbits });
+		let output = encode_update(bytes, self, &mut queue, buffer);
+		*state.bits = queue.bits;
+		state.nbits = queue.nbits;
+		output
+	}
+
+	fn encode_finish<B: EncodeBuf>(&self, state: &mut incremental::State, buffer: B) -> B::Output {
+		let mut queue = Scrub(Queue { bits: *state.bits, nbits: state.nbits });
+		encode_finish(self, &mut queue, buffer)
+	}
+
+	fn decode_update<B: DecodeBuf>(&self, state: &mut incremental::State, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		// The unpaired character is kept as the pending character
+		let mut queue = Scrub(Queue { bits: *state.bits, nbits: state.nbits });
+		let mut digit = Scrub(if state.pending_len > 0 { state.pending[0] as u32 } else { NO_DIGIT });
+		let output = decode_update(string, self, &mut queue, &mut digit, state.offset, buffer)?;
+		*state.bits = queue.bits;
+		state.nbits = queue.nbits;
+		state.pending[0] = *digit as u8;
+		state.pending_len = if *digit != NO_DIGIT { 1 } else { 0 };
+		state.offset += string.len();
+		Ok(output)
+	}
+
+	fn decode_finish<B: DecodeBuf>(&self, state: &mut incremental::State, buffer: B) -> Result<B::Output, Error> {
+		let mut queue = Scrub(Queue { bits: *state.bits, nbits: state.nbits });
+		let digit = if state.pending_len > 0 { state.pending[0] as u32 } else { NO_DIGIT };
+		Ok(decode_finish(&mut queue, digit, buffer))
+	}
+}
+
+impl Base91 {
+	/// Creates a new basE91 alphabet.
+	///
+	/// # Panics
+	///
+	/// Panics if the alphabet contains duplicate or non-ASCII characters.
+	pub const fn new(&charset: &[u8; 91]) -> Self {
+		let mut lut = [255; 128];
+		let mut i = 0;
+		while i < charset.len() {
+			if charset[i] as usize >= lut.len() {
+				panic!("non-ASCII character in basE91 charset");
+			}
+			if lut[charset[i] as usize] != 255 {
+				panic!("duplicate character in basE91 charset");
+			}
+			lut[charset[i] as usize] = i as u8;
+			i += 1;
+		}
+		Base91 { charset, lut }
+	}
+
+	/// Estimates the maximum length of the encoded string given the length of the input bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		// Every 13 bits produce at most two characters, the final bits another two
+		match (len / 13).checked_mul(16) {
+			Some(n) => match n.checked_add((len % 13 * 16).div_ceil(13) + 2) {
+				Some(n) => n,
+				None => ratio::panic_overflow(),
+			},
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the length of the input string.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		// Every two characters produce at most 14 bits, the final character another byte
+		len - len / 8 + 1
+	}
+
+	/// Encodes the input bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let encoded = basenc::Base91Std.encode(b"hello world");
+	/// assert_eq!(encoded, "TPwJh>Io2Tv!lE");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode(bytes, self, std::string::String::new())
+	}
+
+	/// Decodes the input string.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let decoded = basenc::Base91Std.decode("TPwJh>Io2Tv!lE").unwrap();
+	/// assert_eq!(decoded, b"hello world");
+	/// ```
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode(string.as_bytes(), self, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// The buffer must have room for [`Base91::estimate_encoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::Base91;
+	///
+	/// let mut stack_buf = [0u8; Base91::estimate_encoded_len(4)];
+	/// let encoded = basenc::Base91Std.encode_into(b"test", &mut stack_buf);
+	/// assert_eq!(encoded, "fPNKd");
+	/// ```
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`Base91::estimate_decoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::Base91;
+	///
+	/// let mut stack_buf = [0u8; Base91::estimate_decoded_len(5)];
+	/// let decoded = basenc::Base91Std.decode_into("fPNKd", &mut stack_buf).unwrap();
+	/// assert_eq!(decoded, b"test");
+	/// ```
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode(string.as_bytes(), self, buffer)
+	}
+
+	/// Wraps the encoding and bytes for display.
+	#[inline]
+	pub fn display<'a>(&'a self, bytes: &'a [u8]) -> Display<'a, Self> {
+		Display { encoding: self, bytes }
+	}
+
+	/// Returns an encoder to encode in chunks.
+	#[inline]
+	pub const fn encoder(&self) -> Base91Encoder<'_> {
+		Base91Encoder { base: self, queue: Scrub(Queue::new()) }
+	}
+
+	/// Returns a decoder to decode in chunks.
+	#[inline]
+	pub const fn decoder(&self) -> Base91Decoder<'_> {
+		Base91Decoder { base: self, queue: Scrub(Queue::new()), digit: Scrub(NO_DIGIT), position: 0 }
+	}
+}
+
+//----------------------------------------------------------------
+
+/// basE91 encoder.
+///
+/// Keeps the pending bits between calls to [`update`](Self::update), the output is identical to encoding the concatenated input.
+///
+/// # Examples
+///
+/// ```
+/// let mut encoder = basenc::Base91Std.encoder();
+/// let mut stack_buf = [0u8; 32];
+/// let mut string = String::new();
+/// string.push_str(encoder.update(b"hello ", &mut stack_buf));
+/// string.push_str(encoder.update(b"world", &mut stack_buf));
+/// string.push_str(encoder.finish(&mut stack_buf));
+/// assert_eq!(string, "TPwJh>Io2Tv!lE");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Base91Encoder<'a> {
+	base: &'a Base91,
+	queue: Scrub<Queue>,
+}
+
+impl Base91Encoder<'_> {
+	/// Encodes the bytes into a buffer.
+	///
+	/// The buffer must have room for [`Base91::estimate_encoded_len`] of the input length.
+	#[inline]
+	pub fn update<B: EncodeBuf>(&mut self, bytes: &[u8], buffer: B) -> B::Output {
+		encode_update(bytes, self.base, &mut self.queue, buffer)
+	}
+
+	/// Encodes the remaining bits into a buffer.
+	///
+	/// The buffer must have room for two bytes.
+	#[inline]
+	pub fn finish<B: EncodeBuf>(mut self, buffer: B) -> B::Output {
+		encode_finish(self.base, &mut self.queue, buffer)
+	}
+}
+
+/// basE91 decoder.
+///
+/// Keeps the pending bits and character between calls to [`update`](Self::update), the output is identical to decoding the concatenated input.
+///
+/// # Examples
+///
+/// ```
+/// let mut decoder = basenc::Base91Std.decoder();
+/// let mut bytes = Vec::new();
+/// decoder.update("TPwJh>I", &mut bytes).unwrap();
+/// decoder.update("o2Tv!lE", &mut bytes).unwrap();
+/// decoder.finish(&mut bytes);
+/// assert_eq!(bytes, b"hello world");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Base91Decoder<'a> {
+	base: &'a Base91,
+	queue: Scrub<Queue>,
+	digit: Scrub<u32>,
+	position: usize,
+}
+
+impl Base91Decoder<'_> {
+	/// Decodes the string into a buffer.
+	///
+	/// The buffer must have room for [`Base91::estimate_decoded_len`] of the input length.
+	/// Error offsets are relative to the start of the first chunk, on error the decoder is left unchanged.
+	pub fn update<B: DecodeBuf>(&mut self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		let output = decode_update(string.as_bytes(), self.base, &mut self.queue, &mut self.digit, self.position, buffer)?;
+		self.position += string.len();
+		Ok(output)
+	}
+
+	/// Decodes the remaining bits into a buffer.
+	///
+	/// The buffer must have room for one byte.
+	#[inline]
+	pub fn finish<B: DecodeBuf>(mut self, buffer: B) -> B::Output {
+		decode_finish(&mut self.queue, *self.digit, buffer)
+	}
+}
+
+//----------------------------------------------------------------
+
+/// basE91 alphabet by Joachim Henke.
+///
+/// The alphabet is ``A-Za-z0-9!#$%&()*+,./:;<=>?@[]^_`{|}~"``.
+#[allow(non_upper_case_globals)]
+pub static Base91Std: Base91 = Base91::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"");
+
+//----------------------------------------------------------------
+
+// Bits pending between groups, least significant bit first
+#[derive(Copy, Clone)]
+struct Queue {
+	bits: u32,
+	nbits: u32,
+}
+
+impl Queue {
+	const fn new() -> Queue {
+		Queue { bits: 0, nbits: 0 }
+	}
+}
+
+// No pending character when decoding
+const NO_DIGIT: u32 = u32::MAX;
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], base: &Base91, mut buffer: B) -> B::Output {
+	// Without pending bits both estimates hold, accept buffers sized with either
+	let dest_len = cmp::min(RATIO.estimate_encoded_len(bytes.len()), Base91::estimate_encoded_len(bytes.len()));
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let mut queue = Scrub(Queue::new());
+		let end = encode_bytes(bytes, base, &mut queue, dest);
+		let end = encode_final(base, &mut queue, end);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+fn encode_update<B: EncodeBuf>(bytes: &[u8], base: &Base91, queue: &mut Queue, mut buffer: B) -> B::Output {
+	let dest_len = Base91::estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, base, queue, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+fn encode_finish<B: EncodeBuf>(base: &Base91, queue: &mut Queue, mut buffer: B) -> B::Output {
+	unsafe {
+		let dest = buffer.allocate(2);
+		let end = encode_final(base, queue, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+unsafe fn encode_bytes(bytes: &[u8], base: &Base91, queue: &mut Queue, mut dest: *mut u8) -> *mut u8 {
+	let Queue { mut bits, mut nbits } = *queue;
+
+	for &byte in bytes {
+		bits |= (byte as u32) << nbits;
+		nbits += 8;
+		if nbits > 13 {
+			// Take 13 bits unless the value is small enough to take 14 bits
+			let mut value = bits & 0x1fff;
+			if value > 88 {
+				bits >>= 13;
+				nbits -= 13;
+			}
+			else {
+				value = bits & 0x3fff;
+				bits >>= 14;
+				nbits -= 14;
+			}
+			*dest = base.charset[(value % 91) as usize];
+			*dest.add(1) = base.charset[(value / 91) as usize];
+			dest = dest.add(2);
+		}
+	}
+
+	*queue = Queue { bits, nbits };
+	return dest;
+}
+
+unsafe fn encode_final(base: &Base91, queue: &mut Queue, mut dest: *mut u8) -> *mut u8 {
+	let Queue { bits, nbits } = *queue;
+
+	if nbits > 0 {
+		*dest = base.charset[(bits % 91) as usize];
+		dest = dest.add(1);
+		if nbits > 7 || bits > 90 {
+			*dest = base.charset[(bits / 91) as usize];
+			dest = dest.add(1);
+		}
+	}
+
+	*queue = Queue::new();
+	return dest;
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], base: &Base91, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = Base91::estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let mut queue = Scrub(Queue::new());
+		let mut digit = Scrub(NO_DIGIT);
+		let end = decode_string(string, base, &mut queue, &mut digit, 0, dest)?;
+		let end = decode_final(&mut queue, *digit, end);
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+fn decode_update<B: DecodeBuf>(string: &[u8], base: &Base91, queue: &mut Queue, digit: &mut u32, position: usize, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = Base91::estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, base, queue, digit, position, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+fn decode_finish<B: DecodeBuf>(queue: &mut Queue, digit: u32, mut buffer: B) -> B::Output {
+	unsafe {
+		let dest = buffer.allocate(1);
+		let end = decode_final(queue, digit, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+// The state is only updated if the whole string is valid
+unsafe fn decode_string(string: &[u8], base: &Base91, queue: &mut Queue, pending: &mut u32, position: usize, mut dest: *mut u8) -> Result<*mut u8, Error> {
+	let Queue { mut bits, mut nbits } = *queue;
+	let mut digit = *pending;
+
+	for (i, &byte) in string.iter().enumerate() {
+		let value = if byte < 128 { base.lut[byte as usize] } else { 255 };
+		if value >= 91 {
+			return Err(Error::InvalidCharacter { offset: position + i, byte });
+		}
+
+		if digit == NO_DIGIT {
+			digit = value as u32;
+			continue;
+		}
+
+		let value = digit + value as u32 * 91;
+		digit = NO_DIGIT;
+		bits |= value << nbits;
+		nbits += if value & 0x1fff > 88 { 13 } else { 14 };
+		while nbits >= 8 {
+			*dest = bits as u8;
+			dest = dest.add(1);
+			bits >>= 8;
+			nbits -= 8;
+		}
+	}
+
+	*queue = Queue { bits, nbits };
+	*pending = digit;
+	Ok(dest)
+}
+
+unsafe fn decode_final(queue: &mut Queue, digit: u32, mut dest: *mut u8) -> *mut u8 {
+	if digit != NO_DIGIT {
+		*dest = (queue.bits | digit << queue.nbits) as u8;
+		dest = dest.add(1);
+	}
+
+	*queue = Queue::new();
+	return dest;
+}
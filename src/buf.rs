@@ -24,6 +24,10 @@ use core::{mem, slice, str};
 /// Efficient buffer reuse. Appends to the buffer.
 /// - `&mut Vec<u8>`
 ///
+/// Wipes its memory when dropped or reallocated, requires the `zeroize` feature.
+/// - `Zeroizing`
+/// - `&mut Zeroizing`
+///
 /// Stack buffers. Panics if the buffer is too small.
 /// - `&mut [u8]`
 /// - `&mut [u8; N]`
@@ -177,6 +181,10 @@ impl<'a> DecodeBuf for &'a mut ::std::vec::Vec<u8> {
 /// - `&mut String`
 /// - `&mut Vec<u8>`
 ///
+/// Wipes its memory when dropped or reallocated, requires the `zeroize` feature.
+/// - `Zeroizing`
+/// - `&mut Zeroizing`
+///
 /// Stack buffers. Panics if the buffer is too small.
 /// - `&mut [u8]`
 /// - `&mut [u8; N]`
@@ -0,0 +1,165 @@
+/*!
+Constant-time encoding and decoding.
+====================================
+
+The regular encoders index lookup tables with the input and stop at the first invalid character.
+When the input is secret, such as private keys and session tokens, this leaks information through timing side channels.
+
+The encodings in this module compute characters and values with branchless arithmetic instead.
+Valid input is processed without secret-dependent memory accesses or branches.
+
+The structure of the input is not considered secret: the length and the position of padding characters affect timing.
+Invalid input is rejected in variable time to report the location of the error.
+
+Examples
+--------
+
+```
+let encoded = basenc::ct::Base64Url.encode(b"secret key");
+assert_eq!(encoded, "c2VjcmV0IGtleQ");
+
+let decoded = basenc::ct::Base64Url.decode(&encoded).unwrap();
+assert_eq!(decoded, b"secret key");
+
+assert!(basenc::ct::Base64Url.verify(&encoded, b"secret key"));
+assert!(!basenc::ct::Base64Url.verify(&encoded, b"secret kez"));
+```
+*/
+
+use super::*;
+
+/// Constant-time encoding.
+///
+/// Wraps one of the alphabets of this crate, see the [module documentation](self).
+#[derive(Clone, Debug)]
+pub struct ConstantTime<'a, T> {
+	pub(crate) encoding: &'a T,
+}
+
+impl<'a, T> ConstantTime<'a, T> {
+	/// Constructor.
+	#[inline]
+	pub const fn new(encoding: &'a T) -> Self {
+		ConstantTime { encoding }
+	}
+}
+
+impl<T> ConstantTime<'_, T> where Self: Encoding {
+	/// Verifies in constant time that the string decodes to the expected bytes.
+	///
+	/// Returns `false` if the string is invalid or does not match.
+	/// Only the decoded bytes are compared in constant time, the lengths are not considered secret.
+	#[inline]
+	pub fn verify(&self, string: &str, expected: &[u8]) -> bool {
+		verify(self, string.as_bytes(), expected)
+	}
+}
+
+impl<T> WithPad<'_, ConstantTime<'_, T>> where Self: Encoding {
+	/// Verifies in constant time that the string decodes to the expected bytes.
+	///
+	/// Returns `false` if the string is invalid or does not match.
+	/// Only the decoded bytes are compared in constant time, the lengths are not considered secret.
+	#[inline]
+	pub fn verify(&self, string: &str, expected: &[u8]) -> bool {
+		verify(self, string.as_bytes(), expected)
+	}
+}
+
+fn verify<E: Encoding>(encoding: &E, string: &[u8], expected: &[u8]) -> bool {
+	let mut stack_buf = Scrub([0u8; 512]);
+	let chunk_size = E::RATIO.decoding_chunk_size(stack_buf.len()) - E::RATIO.encoded as usize;
+
+	let mut decoder = incremental::Decoder::new(encoding);
+	let mut diff = 0;
+	let mut len = 0;
+
+	let mut compare = |bytes: &[u8]| {
+		for &byte in bytes {
+			match expected.get(len) {
+				Some(&expected) => diff |= byte ^ expected,
+				None => diff |= 1,
+			}
+			len += 1;
+		}
+	};
+
+	for chunk in string.chunks(chunk_size) {
+		match decoder.update(chunk, &mut *stack_buf) {
+			Ok(bytes) => compare(bytes),
+			Err(_) => return false,
+		}
+	}
+	match decoder.finish(&mut *stack_buf) {
+		Ok(bytes) => compare(bytes),
+		Err(_) => return false,
+	}
+
+	diff == 0 && len == expected.len()
+}
+
+//----------------------------------------------------------------
+// Branchless primitives, inputs must be less than 2^31.
+
+/// All bits set if `a < b`.
+#[inline]
+pub(crate) fn lt(a: u32, b: u32) -> u32 {
+	(a.wrapping_sub(b) >> 31).wrapping_neg()
+}
+
+/// All bits set if `a == b`.
+#[inline]
+pub(crate) fn eq(a: u32, b: u32) -> u32 {
+	let x = a ^ b;
+	((x | x.wrapping_neg()) >> 31).wrapping_sub(1)
+}
+
+/// All bits set if `lo <= a <= hi`.
+#[inline]
+pub(crate) fn range(a: u32, lo: u8, hi: u8) -> u32 {
+	!lt(a, lo as u32) & !lt(hi as u32, a)
+}
+
+//----------------------------------------------------------------
+
+/// Constant-time Base64 standard charset.
+///
+/// The alphabet is `A-Za-z0-9+/`.
+#[allow(non_upper_case_globals)]
+pub static Base64Std: ConstantTime<'static, crate::Base64> = ConstantTime::new(&crate::Base64Std);
+
+/// Constant-time Base64 url-safe charset.
+///
+/// The alphabet is `A-Za-z0-9-_`.
+#[allow(non_upper_case_globals)]
+pub static Base64Url: ConstantTime<'static, crate::Base64> = ConstantTime::new(&crate::Base64Url);
+
+/// Constant-time Base32 RFC 4648 alphabet.
+///
+/// The alphabet is `A-Z2-7`.
+#[allow(non_upper_case_globals)]
+pub static Base32Std: ConstantTime<'static, crate::Base32> = ConstantTime::new(&crate::Base32Std);
+
+/// Constant-time Base32 Extended Hex alphabet.
+///
+/// The alphabet is `0-9A-V`.
+#[allow(non_upper_case_globals)]
+pub static Base32Hex: ConstantTime<'static, crate::Base32> = ConstantTime::new(&crate::Base32Hex);
+
+/// Constant-time z-base-32 alphabet.
+///
+/// The alphabet is `ybndrfg8ejkmcpqxot1uwisza345h769`.
+#[allow(non_upper_case_globals)]
+pub static Base32Z: ConstantTime<'static, crate::Base32> = ConstantTime::new(&crate::Base32Z);
+
+/// Constant-time hex charset using lower-case letters.
+///
+/// Decoding accepts both lower- and upper-case letters.
+#[allow(non_upper_case_globals)]
+pub static LowerHex: ConstantTime<'static, crate::LowerHex> = ConstantTime::new(&crate::LowerHex);
+
+/// Constant-time hex charset using upper-case letters.
+///
+/// Decoding accepts both lower- and upper-case letters.
+#[allow(non_upper_case_globals)]
+pub static UpperHex: ConstantTime<'static, crate::UpperHex> = ConstantTime::new(&crate::UpperHex);
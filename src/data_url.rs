@@ -0,0 +1,310 @@
+/*!
+Data URLs.
+==========
+
+[RFC 2397](https://www.rfc-editor.org/rfc/rfc2397) data URLs embed small resources such as images and fonts in HTML and CSS.
+
+A data URL has the form `data:<media type>[;params][;base64],<payload>`.
+The payload is either encoded with [`Base64Std`] or percent-escaped.
+
+Examples
+--------
+
+```
+use basenc::data_url::{self, DataUrl};
+
+let url = DataUrl::new("image/png").base64().encode(b"\x89PNG\r\n\x1a\n");
+assert_eq!(url, "data:image/png;base64,iVBORw0KGgo=");
+
+let (media_type, decoded) = data_url::decode(&url).unwrap();
+assert_eq!(media_type.essence(), "image/png");
+assert_eq!(decoded, b"\x89PNG\r\n\x1a\n");
+```
+*/
+
+use super::*;
+
+const SCHEME: &str = "data:";
+const BASE64: &str = ";base64";
+
+// Leaves quotes and parentheses escaped for embedding in HTML and CSS
+static PAYLOAD: Percent = Percent::new(AsciiSet::UNRESERVED.union(AsciiSet::new(b"!$&*+,;=:@/?")));
+
+/// Data URL builder.
+///
+/// The media type including its parameters is written as is and must not contain a `,`.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::data_url::DataUrl;
+///
+/// let url = DataUrl::new("text/plain;charset=UTF-8").encode("a (b) \"c\"".as_bytes());
+/// assert_eq!(url, "data:text/plain;charset=UTF-8,a%20%28b%29%20%22c%22");
+///
+/// let css = format!("url({})", DataUrl::new("font/woff2").base64().display(b"wOF2"));
+/// assert_eq!(css, "url(data:font/woff2;base64,d09GMg==)");
+/// ```
+#[derive(Clone, Debug)]
+pub struct DataUrl<'a> {
+	media_type: &'a str,
+	base64: bool,
+}
+
+impl<'a> DataUrl<'a> {
+	/// Creates a new data URL builder with the media type and percent-escaped payload.
+	#[inline]
+	pub const fn new(media_type: &'a str) -> DataUrl<'a> {
+		DataUrl { media_type, base64: false }
+	}
+
+	/// With [`Base64Std`] encoded payload.
+	#[inline]
+	pub const fn base64(&self) -> DataUrl<'a> {
+		DataUrl { media_type: self.media_type, base64: true }
+	}
+
+	/// Estimates the maximum length of the data URL given the length of the payload.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(&self, len: usize) -> usize {
+		let payload_len = if self.base64 { <Base64 as Encoding>::RATIO.estimate_encoded_len(len) } else { Percent::estimate_encoded_len(len) };
+		let header_len = SCHEME.len() + self.media_type.len() + if self.base64 { BASE64.len() } else { 0 } + 1;
+		match payload_len.checked_add(header_len) {
+			Some(len) => len,
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Encodes the payload as data URL.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode(bytes, self, std::string::String::new())
+	}
+
+	/// Encodes the payload as data URL into a buffer.
+	///
+	/// The buffer must have room for [`DataUrl::estimate_encoded_len`] bytes.
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	/// Wraps the data URL and payload for display.
+	///
+	/// The payload is encoded in chunks without allocating.
+	#[inline]
+	pub fn display<'b>(&'b self, bytes: &'b [u8]) -> Display<'b, Self> {
+		Display { encoding: self, bytes }
+	}
+}
+
+impl<'a> fmt::Display for Display<'a, DataUrl<'_>> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(SCHEME)?;
+		f.write_str(self.encoding.media_type)?;
+		if self.encoding.base64 {
+			f.write_str(BASE64)?;
+			f.write_str(",")?;
+			fmt::Display::fmt(&Display::new(&Base64Std.pad(Padding::Strict), self.bytes), f)
+		}
+		else {
+			f.write_str(",")?;
+			fmt::Display::fmt(&PAYLOAD.display(self.bytes), f)
+		}
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Media type of a data URL.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MediaType<'a> {
+	essence: &'a str,
+	params: &'a str,
+}
+
+impl<'a> MediaType<'a> {
+	/// Returns the type and subtype without parameters, eg. `image/png`.
+	///
+	/// Defaults to `text/plain` if the data URL omits the media type.
+	#[inline]
+	pub const fn essence(&self) -> &'a str {
+		self.essence
+	}
+
+	/// Returns the parameters as name and value pairs.
+	///
+	/// Values are returned as they appear in the data URL, they are not percent-decoded.
+	#[inline]
+	pub fn params(&self) -> Params<'a> {
+		Params { params: self.params }
+	}
+
+	/// Returns the value of the parameter with the name, compared case-insensitively.
+	///
+	/// Defaults the `charset` to `US-ASCII` if the data URL omits the media type.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::data_url;
+	///
+	/// let (media_type, decoded) = data_url::decode("data:,A%20brief%20note").unwrap();
+	/// assert_eq!(media_type.essence(), "text/plain");
+	/// assert_eq!(media_type.param("charset"), Some("US-ASCII"));
+	/// assert_eq!(decoded, b"A brief note");
+	/// ```
+	#[inline]
+	pub fn param(&self, name: &str) -> Option<&'a str> {
+		self.params().find(|&(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value)
+	}
+}
+
+/// Iterator over the parameters of a [`MediaType`].
+#[derive(Clone, Debug)]
+pub struct Params<'a> {
+	params: &'a str,
+}
+
+impl<'a> Iterator for Params<'a> {
+	type Item = (&'a str, &'a str);
+
+	fn next(&mut self) -> Option<(&'a str, &'a str)> {
+		loop {
+			let params = self.params.strip_prefix(';')?;
+			let (param, rest) = match params.find(';') {
+				Some(index) => params.split_at(index),
+				None => (params, ""),
+			};
+			self.params = rest;
+			if !param.is_empty() {
+				return Some(param.split_once('=').unwrap_or((param, "")));
+			}
+		}
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Estimates the maximum length of the decoded payload given the length of the data URL.
+#[inline]
+pub const fn estimate_decoded_len(len: usize) -> usize {
+	len
+}
+
+/// Decodes the data URL into its media type and payload.
+///
+/// Returns [`Error::InvalidCharacter`] if the string does not start with `data:`, [`Error::IncorrectLength`] if the `,` is missing and the errors of decoding the payload.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::data_url;
+///
+/// let (media_type, decoded) = data_url::decode("data:text/plain;charset=UTF-8;base64,Q2Fmw6k=").unwrap();
+/// assert_eq!(media_type.essence(), "text/plain");
+/// assert_eq!(media_type.params().collect::<Vec<_>>(), [("charset", "UTF-8")]);
+/// assert_eq!(decoded, "Café".as_bytes());
+///
+/// assert_eq!(data_url::decode("date:,"), Err(basenc::Error::InvalidCharacter { offset: 3, byte: b'e' }));
+/// assert_eq!(data_url::decode("data:text/plain"), Err(basenc::Error::IncorrectLength));
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn decode(string: &str) -> Result<(MediaType<'_>, std::vec::Vec<u8>), Error> {
+	decode_into(string, std::vec::Vec::new())
+}
+
+/// Decodes the data URL into its media type and a buffer receiving the payload.
+///
+/// The buffer must have room for [`estimate_decoded_len`] bytes.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::data_url;
+///
+/// let url = "data:image/gif;base64,R0lGODlhAQABAAAAACw=";
+/// let mut stack_buf = [0u8; 64];
+/// let (media_type, decoded) = data_url::decode_into(url, &mut stack_buf[..]).unwrap();
+/// assert_eq!(media_type.essence(), "image/gif");
+/// assert_eq!(decoded, b"GIF89a\x01\x00\x01\x00\x00\x00\x00,");
+/// ```
+#[inline(never)]
+pub fn decode_into<B: DecodeBuf>(string: &str, mut buffer: B) -> Result<(MediaType<'_>, B::Output), Error> {
+	// The scheme is case-insensitive
+	for (i, &byte) in string.as_bytes().iter().take(SCHEME.len()).enumerate() {
+		if !byte.eq_ignore_ascii_case(&SCHEME.as_bytes()[i]) {
+			return Err(Error::InvalidCharacter { offset: i, byte });
+		}
+	}
+	if string.len() < SCHEME.len() {
+		return Err(Error::IncorrectLength);
+	}
+
+	let start = SCHEME.len();
+	let comma = string[start..].find(',').ok_or(Error::IncorrectLength)? + start;
+	let mut header = &string[start..comma];
+	let payload = &string[comma + 1..];
+
+	// Compare bytes, the media type may contain multi-byte characters
+	let base64 = match header.as_bytes().get(header.len().wrapping_sub(BASE64.len())..) {
+		Some(suffix) => suffix.eq_ignore_ascii_case(BASE64.as_bytes()),
+		None => false,
+	};
+	if base64 {
+		header = &header[..header.len() - BASE64.len()];
+	}
+	let media_type = match header.find(';') {
+		Some(0) => MediaType { essence: "text/plain", params: header },
+		Some(index) => MediaType { essence: &header[..index], params: &header[index..] },
+		None if header.is_empty() => MediaType { essence: "text/plain", params: ";charset=US-ASCII" },
+		None => MediaType { essence: header, params: "" },
+	};
+
+	let dest_len = estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let tail = slice::from_raw_parts_mut(dest as *mut mem::MaybeUninit<u8>, dest_len);
+		let result = if base64 {
+			Encoding::decode_into(&Base64Std, payload.as_bytes(), tail).map(|bytes| bytes.len())
+		}
+		else {
+			PAYLOAD.decode_into(payload, tail).map(|bytes| bytes.len())
+		};
+		let len = result.map_err(|err| err.offset_by(comma + 1))?;
+		Ok((media_type, buffer.commit(len)))
+	}
+}
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], url: &DataUrl, mut buffer: B) -> B::Output {
+	let dest_len = url.estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let mut header = dest;
+		for part in [SCHEME, url.media_type, if url.base64 { BASE64 } else { "" }, ","] {
+			ptr::copy_nonoverlapping(part.as_ptr(), header, part.len());
+			header = header.add(part.len());
+		}
+		let header_len = header.offset_from(dest) as usize;
+		let tail = slice::from_raw_parts_mut(header as *mut mem::MaybeUninit<u8>, dest_len - header_len);
+		let len = if url.base64 {
+			Base64Std.pad(Padding::Strict).encode_into(bytes, tail).len()
+		}
+		else {
+			PAYLOAD.encode_into(bytes, tail).len()
+		};
+		buffer.commit(header_len + len)
+	}
+}
@@ -0,0 +1,349 @@
+/*!
+MIME encoded-words.
+===================
+
+[RFC 2047](https://www.rfc-editor.org/rfc/rfc2047) encoded-words carry non-ASCII text in email headers, eg. `=?UTF-8?Q?caf=C3=A9?=`.
+
+The text is encoded with either the `B` encoding, [`Base64Std`] with padding, or the `Q` encoding, a variant of quoted-printable.
+The encoder picks whichever is shorter and splits long text into multiple encoded-words of at most 75 characters.
+
+Examples
+--------
+
+```
+use basenc::encoded_word;
+
+let encoded = encoded_word::encode("Café au lait");
+assert_eq!(encoded, "=?UTF-8?Q?Caf=C3=A9_au_lait?=");
+
+let decoded = encoded_word::decode(&encoded).unwrap();
+assert_eq!(decoded, "Café au lait".as_bytes());
+```
+*/
+
+use super::*;
+
+/// Maximum length of an encoded-word.
+const WORD_LEN: usize = 75;
+
+/// Prefix of the encoded-words, the encoding character follows.
+const PREFIX: &[u8] = b"=?UTF-8?";
+
+/// Separator between encoded-words, folding the header line.
+const SEPARATOR: &[u8] = b"\r\n ";
+
+/// Maximum length of the encoded text in an encoded-word.
+const TEXT_LEN: usize = WORD_LEN - PREFIX.len() - 4;
+
+/// Maximum number of bytes in a `B` encoded-word.
+const B_BYTES: usize = TEXT_LEN / 4 * 3;
+
+/// Estimates the maximum length of the encoded string given the length of the input text.
+///
+/// # Panics
+///
+/// Panics if the result overflows `usize`.
+#[inline]
+pub const fn estimate_encoded_len(len: usize) -> usize {
+	// Every encoded-word but the last holds at least a full `B` encoded-word minus an incomplete UTF-8 sequence
+	let words = len / (B_BYTES - 3) + 1;
+	match words.checked_mul(WORD_LEN + SEPARATOR.len()) {
+		Some(len) => len,
+		None => ratio::panic_overflow(),
+	}
+}
+
+/// Estimates the maximum length of the decoded bytes given the length of the input string.
+#[inline]
+pub const fn estimate_decoded_len(len: usize) -> usize {
+	len
+}
+
+/// Encodes the text as UTF-8 encoded-words.
+///
+/// Encoded-words are separated by a folding line break `\r\n `.
+/// Empty text is encoded as an empty string.
+#[cfg(feature = "std")]
+#[inline]
+pub fn encode(text: &str) -> std::string::String {
+	encode_into(text, std::string::String::new())
+}
+
+/// Decodes the encoded-words in a header value.
+///
+/// Text outside of encoded-words is passed through, whitespace between adjacent encoded-words is removed.
+/// Malformed encoded-words are passed through as text.
+///
+/// The charset of the encoded-words is not interpreted, the decoded bytes are joined as they are.
+///
+/// Returns [`Error::InvalidCharacter`] for an invalid character in the encoded text and [`Error::IncorrectLength`] for a `B` encoded text of incorrect length.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::encoded_word;
+///
+/// let decoded = encoded_word::decode("Re: =?utf-8?b?Q2Fmw6k=?=\r\n =?UTF-8?q?_au_lait?= (=?ISO-8859-1?Q?caf=E9?=)").unwrap();
+/// assert_eq!(decoded, b"Re: Caf\xC3\xA9 au lait (caf\xE9)");
+///
+/// assert_eq!(encoded_word::decode("=?UTF-8?Q?a=3?="), Err(basenc::Error::InvalidCharacter { offset: 13, byte: b'?' }));
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn decode(string: &str) -> Result<std::vec::Vec<u8>, Error> {
+	decode_into(string, std::vec::Vec::new())
+}
+
+/// Encodes the text as UTF-8 encoded-words into a buffer.
+///
+/// The buffer must have room for [`estimate_encoded_len`] bytes.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::encoded_word;
+///
+/// let mut stack_buf = [0u8; encoded_word::estimate_encoded_len(8)];
+/// let encoded = encoded_word::encode_into("€€€", &mut stack_buf);
+/// assert_eq!(encoded, "=?UTF-8?B?4oKs4oKs4oKs?=");
+/// ```
+#[inline(never)]
+pub fn encode_into<B: EncodeBuf>(text: &str, mut buffer: B) -> B::Output {
+	let bytes = text.as_bytes();
+	let dest_len = estimate_encoded_len(bytes.len());
+
+	// Pick the shorter encoding
+	let scheme = if encoded_len(bytes, Scheme::Q) <= encoded_len(bytes, Scheme::B) { Scheme::Q } else { Scheme::B };
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, scheme, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+/// Decodes the encoded-words in a header value into a buffer.
+///
+/// The buffer must have room for [`estimate_decoded_len`] bytes.
+#[inline(never)]
+pub fn decode_into<B: DecodeBuf>(string: &str, mut buffer: B) -> Result<B::Output, Error> {
+	let string = string.as_bytes();
+	let dest_len = estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+//----------------------------------------------------------------
+// Encoding
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Scheme {
+	B,
+	Q,
+}
+
+#[inline]
+fn is_q_literal(byte: u8) -> bool {
+	// Characters allowed in a phrase, see RFC 2047 section 5 (3)
+	const Q_LITERAL: AsciiSet = AsciiSet::ALPHANUMERIC.union(AsciiSet::new(b"!*+-/"));
+	Q_LITERAL.contains(byte)
+}
+
+#[inline]
+fn q_len(byte: u8) -> usize {
+	if byte == b' ' || is_q_literal(byte) { 1 } else { 3 }
+}
+
+// Returns the number of bytes and encoded length of the next encoded-word
+fn next_word(bytes: &[u8], scheme: Scheme) -> (usize, usize) {
+	match scheme {
+		Scheme::B => {
+			let mut n = cmp::min(bytes.len(), B_BYTES);
+			// Do not split a UTF-8 sequence
+			while n < bytes.len() && bytes[n] & 0xC0 == 0x80 {
+				n -= 1;
+			}
+			(n, n.div_ceil(3) * 4)
+		},
+		Scheme::Q => {
+			let mut n = 0;
+			let mut len = 0;
+			while n < bytes.len() {
+				let mut end = n + 1;
+				while end < bytes.len() && bytes[end] & 0xC0 == 0x80 {
+					end += 1;
+				}
+				let char_len: usize = bytes[n..end].iter().map(|&byte| q_len(byte)).sum();
+				if n > 0 && len + char_len > TEXT_LEN {
+					break;
+				}
+				n = end;
+				len += char_len;
+			}
+			(n, len)
+		},
+	}
+}
+
+fn encoded_len(mut bytes: &[u8], scheme: Scheme) -> usize {
+	let mut len = 0;
+	while !bytes.is_empty() {
+		let (n, text_len) = next_word(bytes, scheme);
+		len += PREFIX.len() + 4 + text_len + SEPARATOR.len();
+		bytes = &bytes[n..];
+	}
+	len
+}
+
+#[inline]
+unsafe fn write(chunk: &[u8], dest: *mut u8) -> *mut u8 {
+	ptr::copy_nonoverlapping(chunk.as_ptr(), dest, chunk.len());
+	dest.add(chunk.len())
+}
+
+unsafe fn encode_bytes(mut bytes: &[u8], scheme: Scheme, mut dest: *mut u8) -> *mut u8 {
+	let mut first = true;
+	while !bytes.is_empty() {
+		let (n, text_len) = next_word(bytes, scheme);
+		let (word, rest) = bytes.split_at(n);
+		bytes = rest;
+
+		if !first {
+			dest = write(SEPARATOR, dest);
+		}
+		first = false;
+
+		dest = write(PREFIX, dest);
+		match scheme {
+			Scheme::B => {
+				dest = write(b"B?", dest);
+				let tail = slice::from_raw_parts_mut(dest as *mut mem::MaybeUninit<u8>, text_len);
+				Base64Std.pad(Padding::Strict).encode_into(word, tail);
+			},
+			Scheme::Q => {
+				dest = write(b"Q?", dest);
+				let mut p = dest;
+				for &byte in word {
+					if byte == b' ' {
+						*p = b'_';
+					}
+					else if is_q_literal(byte) {
+						*p = byte;
+					}
+					else {
+						*p = b'=';
+						*p.add(1) = hex::encode::encode_char(byte >> 4, b'A');
+						*p.add(2) = hex::encode::encode_char(byte & 0xf, b'A');
+					}
+					p = p.add(q_len(byte));
+				}
+			},
+		}
+		dest = write(b"?=", dest.add(text_len));
+	}
+
+	return dest;
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+// Location of an encoded-word in the input string
+struct Word {
+	scheme: Scheme,
+	text: core::ops::Range<usize>,
+	end: usize,
+}
+
+#[inline]
+fn is_word_char(byte: u8) -> bool {
+	byte > b' ' && byte < 127 && byte != b'?'
+}
+
+// Parses an encoded-word `=?charset?encoding?text?=` at the start
+fn parse_word(string: &[u8], start: usize) -> Option<Word> {
+	let rest = string.get(start..)?;
+	if !rest.starts_with(b"=?") {
+		return None;
+	}
+	let charset = rest[2..].iter().position(|&chr| !is_word_char(chr))? + 2;
+	if charset == 2 || rest.get(charset) != Some(&b'?') {
+		return None;
+	}
+	let scheme = match rest.get(charset + 1..charset + 3)? {
+		b"B?" | b"b?" => Scheme::B,
+		b"Q?" | b"q?" => Scheme::Q,
+		_ => return None,
+	};
+	let text_start = charset + 3;
+	let text_end = rest[text_start..].iter().position(|&chr| !is_word_char(chr))? + text_start;
+	if !rest[text_end..].starts_with(b"?=") {
+		return None;
+	}
+	Some(Word {
+		scheme,
+		text: start + text_start..start + text_end,
+		end: start + text_end + 2,
+	})
+}
+
+unsafe fn decode_word(string: &[u8], word: &Word, mut dest: *mut u8) -> Result<*mut u8, Error> {
+	match word.scheme {
+		Scheme::B => {
+			let text = &string[word.text.clone()];
+			let len = <Base64 as Encoding>::RATIO.estimate_decoded_len(text.len());
+			let tail = slice::from_raw_parts_mut(dest as *mut mem::MaybeUninit<u8>, len);
+			let decoded = Encoding::decode_into(&Base64Std, text, tail).map_err(|err| err.offset_by(word.text.start))?;
+			dest = dest.add(decoded.len());
+		},
+		Scheme::Q => {
+			let mut i = word.text.start;
+			while i < word.text.end {
+				*dest = match string[i] {
+					b'_' => b' ',
+					// The escape is followed by at least the `?=` of the encoded-word
+					b'=' => {
+						let byte = hex::decode::decode_nibble(string, i + 1)? << 4 | hex::decode::decode_nibble(string, i + 2)?;
+						i += 2;
+						byte
+					},
+					byte => byte,
+				};
+				dest = dest.add(1);
+				i += 1;
+			}
+		},
+	}
+	Ok(dest)
+}
+
+unsafe fn decode_string(string: &[u8], mut dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut i = 0;
+	while i < string.len() {
+		let Some(word) = parse_word(string, i) else {
+			*dest = string[i];
+			dest = dest.add(1);
+			i += 1;
+			continue;
+		};
+		dest = decode_word(string, &word, dest)?;
+		i = word.end;
+
+		// Remove the whitespace between adjacent encoded-words
+		let mut next = i;
+		while next < string.len() && matches!(string[next], b' ' | b'\t' | b'\r' | b'\n') {
+			next += 1;
+		}
+		if next > i && parse_word(string, next).is_some() {
+			i = next;
+		}
+	}
+
+	Ok(dest)
+}
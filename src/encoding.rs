@@ -64,3 +64,26 @@ macro_rules! impl_encoding {
 		}
 	};
 }
+
+// Whitespace adapters, not generated for alphabets containing whitespace such as Base45
+macro_rules! impl_whitespace {
+	($name:path) => {
+		impl $name {
+			/// Ignores ASCII whitespace while decoding.
+			#[inline]
+			pub const fn skip_whitespace(&self) -> crate::SkipWhitespace<'_, Self> {
+				crate::SkipWhitespace::new(self)
+			}
+
+			/// Wraps the encoded output in lines of `width` characters.
+			///
+			/// # Panics
+			///
+			/// Panics if the width is zero.
+			#[inline]
+			pub const fn wrap(&self, width: usize, ending: crate::LineEnding) -> crate::Wrap<'_, Self> {
+				crate::Wrap::new(self, width, ending)
+			}
+		}
+	};
+}
@@ -48,6 +48,7 @@ impl_encoding!(LowerHex,
 		"```",
 	],
 );
+impl_whitespace!(LowerHex);
 
 //----------------------------------------------------------------
 
@@ -98,11 +99,12 @@ impl_encoding!(UpperHex,
 		"```",
 	],
 );
+impl_whitespace!(UpperHex);
 
 //----------------------------------------------------------------
 // Encoding
 
-mod encode;
+pub(crate) mod encode;
 
 #[inline(never)]
 fn encode<B: EncodeBuf>(bytes: &[u8], base: u8, mut buffer: B) -> B::Output {
@@ -119,7 +121,7 @@ fn encode<B: EncodeBuf>(bytes: &[u8], base: u8, mut buffer: B) -> B::Output {
 //----------------------------------------------------------------
 // Decoding
 
-mod decode;
+pub(crate) mod decode;
 
 #[inline(never)]
 fn decode<B: DecodeBuf>(string: &[u8], mut buffer: B) -> Result<B::Output, Error> {
@@ -132,3 +134,20 @@ fn decode<B: DecodeBuf>(string: &[u8], mut buffer: B) -> Result<B::Output, Error
 		Ok(buffer.commit(len))
 	}
 }
+
+//----------------------------------------------------------------
+// Constant-time
+
+mod ct;
+
+//----------------------------------------------------------------
+// Percent-encoding
+
+mod percent;
+pub use self::percent::*;
+
+//----------------------------------------------------------------
+// Quoted-printable
+
+mod qp;
+pub use self::qp::*;
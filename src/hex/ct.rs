@@ -0,0 +1,122 @@
+use super::*;
+use crate::ct::{lt, range, ConstantTime};
+
+impl Encoding for ConstantTime<'_, LowerHex> {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, b'a', buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, buffer)
+	}
+}
+
+impl_encoding!(ConstantTime<'_, LowerHex>);
+impl_whitespace!(ConstantTime<'_, LowerHex>);
+
+impl Encoding for ConstantTime<'_, UpperHex> {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, b'A', buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		decode(string, buffer)
+	}
+}
+
+impl_encoding!(ConstantTime<'_, UpperHex>);
+impl_whitespace!(ConstantTime<'_, UpperHex>);
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline]
+fn encode_char(nibble: u32, base: u8) -> u8 {
+	(nibble + b'0' as u32 + (lt(9, nibble) & (base - b'0' - 10) as u32)) as u8
+}
+
+unsafe fn encode_bytes(bytes: &[u8], mut dest: *mut u8, base: u8) -> *mut u8 {
+	for &byte in bytes {
+		*dest.add(0) = encode_char((byte >> 4) as u32, base);
+		*dest.add(1) = encode_char((byte & 0xf) as u32, base);
+		dest = dest.add(2);
+	}
+
+	return dest;
+}
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], base: u8, mut buffer: B) -> B::Output {
+	let dest_len = RATIO.estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, dest, base);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+/// Returns the value and a mask with all bits set if the character is valid.
+#[inline]
+fn decode_nibble(chr: u8) -> (u32, u32) {
+	let chr = chr as u32;
+	let digit = range(chr, b'0', b'9');
+	let letter = range(chr | 0x20, b'a', b'f');
+	let value =
+		digit & chr.wrapping_sub(b'0' as u32) |
+		letter & (chr | 0x20).wrapping_sub((b'a' - 10) as u32);
+	(value, digit | letter)
+}
+
+unsafe fn decode_string(string: &[u8], dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut invalid = 0;
+	let mut end = dest;
+
+	for pair in string.chunks_exact(2) {
+		let (hi, hi_valid) = decode_nibble(pair[0]);
+		let (lo, lo_valid) = decode_nibble(pair[1]);
+		*end = (hi << 4 | lo) as u8;
+		end = end.add(1);
+		invalid |= !(hi_valid & lo_valid);
+	}
+
+	if invalid != 0 {
+		return Err(locate(string));
+	}
+	if !string.len().is_multiple_of(2) {
+		return Err(Error::IncorrectLength);
+	}
+
+	Ok(end)
+}
+
+// Locates the invalid character found above, the input is not secret once rejected
+#[cold]
+fn locate(string: &[u8]) -> Error {
+	let offset = string.iter().position(|&chr| decode_nibble(chr).1 == 0).unwrap_or(0);
+	Error::InvalidCharacter { offset, byte: string[offset] }
+}
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = RATIO.estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
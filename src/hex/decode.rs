@@ -10,6 +10,7 @@ pub unsafe fn decode(string: &[u8], dest: *mut u8) -> Result<*mut u8, crate::Err
 */
 
 mod scalar;
+pub use self::scalar::decode_nibble;
 
 impl_arch_decode! {
 	unsafe fn(string: &[u8], dest: *mut u8) -> Result<*mut u8, crate::Error>;
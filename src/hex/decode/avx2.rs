@@ -34,6 +34,8 @@ unsafe fn nib2bytes(a1: __m256i, b1: __m256i, a2: __m256i, b2: __m256i) -> __m25
 
 #[target_feature(enable = "avx2")]
 pub unsafe fn decode(mut string: &[u8], mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let input_len = string.len();
+
 	let a_mask = _mm256_setr_epi8(
 		0, -1, 2, -1, 4, -1, 6, -1, 8, -1, 10, -1, 12, -1, 14, -1,
 		0, -1, 2, -1, 4, -1, 6, -1, 8, -1, 10, -1, 12, -1, 14, -1);
@@ -65,4 +67,5 @@ pub unsafe fn decode(mut string: &[u8], mut dest: *mut u8) -> Result<*mut u8, cr
 	}
 
 	scalar::decode(string, dest)
+		.map_err(|err| err.offset_by(input_len - string.len()))
 }
@@ -1,21 +1,24 @@
 
-fn decode_nibble(byte: u8) -> Result<u8, crate::Error> {
+#[inline]
+pub fn decode_nibble(string: &[u8], i: usize) -> Result<u8, crate::Error> {
+	let byte = string[i];
 	match byte {
 		b'0'..=b'9' => Ok(byte - b'0'),
 		b'a'..=b'f' => Ok(byte - b'a' + 10),
 		b'A'..=b'F' => Ok(byte - b'A' + 10),
-		_ => Err(crate::Error::InvalidCharacter),
+		_ => Err(crate::Error::InvalidCharacter { offset: i, byte }),
 	}
 }
 
-pub unsafe fn decode(mut string: &[u8], mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
-	while let &[hi, lo, ref rest @ ..] = string {
-		*dest = decode_nibble(hi)? << 4 | decode_nibble(lo)?;
+pub unsafe fn decode(string: &[u8], mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let mut i = 0;
+	while i + 2 <= string.len() {
+		*dest = decode_nibble(string, i)? << 4 | decode_nibble(string, i + 1)?;
 		dest = dest.add(1);
-		string = rest;
+		i += 2;
 	}
 
-	if string.len() != 0 {
+	if i != string.len() {
 		return Err(crate::Error::IncorrectLength);
 	}
 
@@ -8,12 +8,12 @@ use super::*;
 // http://0x80.pl/notesen/2022-01-17-validating-hex-parse.html#algorithm-3-by-geoff-langdale
 #[inline]
 #[target_feature(enable = "sse2")]
-unsafe fn decode_hex(v: __m128i) -> Result<__m128i, crate::Error> {
+unsafe fn decode_hex(v: __m128i) -> Result<__m128i, ()> {
 	// Move digits '0'..'9' into range 0xf6..0xff.
 	let t1 = _mm_add_epi8(v, _mm_set1_epi8((0xff - b'9') as i8));
 
 	// And then correct the range to 0xf0..0xf9. All other bytes become less than 0xf0.
-	let t2 = _mm_sub_epi8(t1, _mm_set1_epi8(6));
+	let t2 = _mm_subs_epu8(t1, _mm_set1_epi8(6));
 
 	// Convert '0'..'9' into nibbles 0..9. Non-digit bytes become greater than 0x0f.
 	let t3 = _mm_sub_epi8(t2, _mm_set1_epi8(0xf0u8 as i8));
@@ -34,7 +34,7 @@ unsafe fn decode_hex(v: __m128i) -> Result<__m128i, crate::Error> {
 	let t8 = _mm_adds_epu8(t7, _mm_set1_epi8(127-15));
 
 	if _mm_movemask_epi8(t8) != 0 {
-		return Err(crate::Error::InvalidCharacter);
+		return Err(());
 	}
 
 	Ok(t7)
@@ -63,9 +63,15 @@ unsafe fn nibbles2bytes(result: __m128i) -> __m128i {
 
 #[target_feature(enable = "sse2")]
 pub unsafe fn decode(mut string: &[u8], mut dest: *mut u8) -> Result<*mut u8, crate::Error> {
+	let input_len = string.len();
+
 	while string.len() >= 16 {
 		let v1 = _mm_loadu_si128(string.as_ptr() as *const __m128i);
-		let v2 = decode_hex(v1)?;
+		let Ok(v2) = decode_hex(v1)
+		else {
+			// Locate the error with the scalar code path
+			break;
+		};
 		let v3 = nibbles2bytes(v2);
 		_mm_storeu_si64(dest, v3);
 
@@ -74,4 +80,5 @@ pub unsafe fn decode(mut string: &[u8], mut dest: *mut u8) -> Result<*mut u8, cr
 	}
 
 	scalar::decode(string, dest as *mut u8)
+		.map_err(|err| err.offset_by(input_len - string.len()))
 }
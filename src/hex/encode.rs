@@ -11,6 +11,7 @@ pub unsafe fn encode(bytes: &[u8], mut dest: *mut u8, base: u8) -> *mut u8;
 */
 
 mod scalar;
+pub use self::scalar::encode_char;
 
 impl_arch_encode! {
 	unsafe fn(bytes: &[u8], dest: *mut u8, base: u8) -> *mut u8;
@@ -1,5 +1,5 @@
 
-fn encode_char(nibble: u8, base: u8) -> u8 {
+pub fn encode_char(nibble: u8, base: u8) -> u8 {
 	nibble + if nibble < 10 { b'0' } else { base - 10 }
 }
 
@@ -0,0 +1,318 @@
+use super::*;
+
+/// Set of ASCII characters.
+///
+/// Used by [`Percent`] as the set of characters which are not escaped.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AsciiSet {
+	bits: u128,
+}
+
+impl AsciiSet {
+	/// The empty set.
+	pub const EMPTY: AsciiSet = AsciiSet { bits: 0 };
+
+	/// ASCII letters and digits.
+	pub const ALPHANUMERIC: AsciiSet = AsciiSet::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+
+	/// RFC 3986 unreserved characters, `A-Za-z0-9-._~`.
+	pub const UNRESERVED: AsciiSet = AsciiSet::ALPHANUMERIC.union(AsciiSet::new(b"-._~"));
+
+	/// RFC 3986 sub-delimiters, ``!$&'()*+,;=``.
+	pub const SUB_DELIMS: AsciiSet = AsciiSet::new(b"!$&'()*+,;=");
+
+	/// Creates a set of the given characters.
+	///
+	/// # Panics
+	///
+	/// Panics if a character is not ASCII.
+	pub const fn new(chars: &[u8]) -> AsciiSet {
+		let mut set = AsciiSet::EMPTY;
+		let mut i = 0;
+		while i < chars.len() {
+			set = set.add(chars[i]);
+			i += 1;
+		}
+		set
+	}
+
+	/// Adds a character to the set.
+	///
+	/// # Panics
+	///
+	/// Panics if the character is not ASCII.
+	#[inline]
+	pub const fn add(self, chr: u8) -> AsciiSet {
+		assert!(chr < 128, "non-ASCII character in AsciiSet");
+		AsciiSet { bits: self.bits | 1 << chr }
+	}
+
+	/// Removes a character from the set.
+	#[inline]
+	pub const fn remove(self, chr: u8) -> AsciiSet {
+		if chr >= 128 {
+			return self;
+		}
+		AsciiSet { bits: self.bits & !(1 << chr) }
+	}
+
+	/// Returns the union of two sets.
+	#[inline]
+	pub const fn union(self, other: AsciiSet) -> AsciiSet {
+		AsciiSet { bits: self.bits | other.bits }
+	}
+
+	/// Returns if the set contains the byte.
+	#[inline]
+	pub const fn contains(&self, byte: u8) -> bool {
+		byte < 128 && self.bits >> byte & 1 != 0
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Percent-encoding as specified by RFC 3986.
+///
+/// Bytes outside the set of allowed characters are escaped as `%XX` with upper-case hex digits.
+/// Bytes which are not ASCII and `%` itself are always escaped.
+///
+/// Only escaped bytes grow to three characters, the output length depends on the bytes and not just their number.
+/// Size buffers with [`Percent::estimate_encoded_len`], which assumes every byte is escaped.
+///
+/// Decoding accepts upper- and lower-case hex digits, all other characters are passed through.
+///
+/// # Examples
+///
+/// ```
+/// let encoded = basenc::PercentPathSegment.encode(b"hello world/1+1=2");
+/// assert_eq!(encoded, "hello%20world%2F1+1=2");
+///
+/// let decoded = basenc::PercentPathSegment.decode(&encoded).unwrap();
+/// assert_eq!(decoded, b"hello world/1+1=2");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Percent {
+	set: AsciiSet,
+	plus: bool,
+}
+
+impl Percent {
+	/// Creates a new percent-encoding leaving the characters in the set unescaped.
+	#[inline]
+	pub const fn new(set: AsciiSet) -> Percent {
+		Percent { set: set.remove(b'%'), plus: false }
+	}
+
+	/// With space encoded as `+`.
+	///
+	/// Decoding turns `+` into space, the set should not contain `+`.
+	#[inline]
+	pub const fn space_as_plus(&self) -> Percent {
+		Percent { set: self.set.remove(b' '), plus: true }
+	}
+
+	/// Estimates the maximum length of the encoded string given the length of the input bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		match len.checked_mul(3) {
+			Some(len) => len,
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the length of the input string.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		len
+	}
+
+	/// Encodes the input bytes.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode(bytes, self, std::string::String::new())
+	}
+
+	/// Decodes the input string.
+	///
+	/// Returns [`Error::InvalidCharacter`] for an escape with invalid hex digits and [`Error::IncorrectLength`] if the string ends in an incomplete escape.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode(string.as_bytes(), self, std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// The buffer must have room for [`Percent::estimate_encoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut stack_buf = [0u8; 64];
+	/// let encoded = basenc::PercentForm.encode_into(b"name=J\xC3\xBCrgen & co", &mut stack_buf);
+	/// assert_eq!(encoded, "name%3DJ%C3%BCrgen+%26+co");
+	/// ```
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`Percent::estimate_decoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// let mut stack_buf = [0u8; 32];
+	/// let decoded = basenc::PercentForm.decode_into("J%c3%bcrgen+%26+co", &mut stack_buf).unwrap();
+	/// assert_eq!(decoded, "Jürgen & co".as_bytes());
+	///
+	/// assert_eq!(basenc::PercentForm.decode("100%"), Err(basenc::Error::IncorrectLength));
+	/// assert_eq!(basenc::PercentForm.decode("100%!"), Err(basenc::Error::InvalidCharacter { offset: 4, byte: b'!' }));
+	/// ```
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode(string.as_bytes(), self, buffer)
+	}
+
+	/// Wraps the encoding and bytes for display.
+	#[inline]
+	pub fn display<'a>(&'a self, bytes: &'a [u8]) -> Display<'a, Self> {
+		Display { encoding: self, bytes }
+	}
+}
+
+impl<'a> fmt::Display for Display<'a, Percent> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut stack_buf = Scrub(mem::MaybeUninit::<[u8; 512]>::uninit());
+		let chunk_size = mem::size_of_val(&stack_buf) / 3;
+
+		for chunk in self.bytes.chunks(chunk_size) {
+			f.write_str(self.encoding.encode_into(chunk, &mut *stack_buf))?;
+		}
+
+		Ok(())
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Percent-encoding of a path segment.
+///
+/// Leaves the RFC 3986 unreserved characters, sub-delimiters, `:` and `@` unescaped.
+#[allow(non_upper_case_globals)]
+pub static PercentPathSegment: Percent = Percent::new(AsciiSet::UNRESERVED.union(AsciiSet::SUB_DELIMS).union(AsciiSet::new(b":@")));
+
+/// Percent-encoding of a query.
+///
+/// Leaves the RFC 3986 unreserved characters, sub-delimiters, `:`, `@`, `/` and `?` unescaped.
+/// Use [`PercentForm`] to encode the names and values of form data.
+#[allow(non_upper_case_globals)]
+pub static PercentQuery: Percent = Percent::new(AsciiSet::UNRESERVED.union(AsciiSet::SUB_DELIMS).union(AsciiSet::new(b":@/?")));
+
+/// Percent-encoding of a fragment.
+///
+/// Leaves the RFC 3986 unreserved characters, sub-delimiters, `:`, `@`, `/` and `?` unescaped.
+#[allow(non_upper_case_globals)]
+pub static PercentFragment: Percent = Percent::new(AsciiSet::UNRESERVED.union(AsciiSet::SUB_DELIMS).union(AsciiSet::new(b":@/?")));
+
+/// Percent-encoding of the user information.
+///
+/// Leaves the RFC 3986 unreserved characters, sub-delimiters and `:` unescaped.
+#[allow(non_upper_case_globals)]
+pub static PercentUserinfo: Percent = Percent::new(AsciiSet::UNRESERVED.union(AsciiSet::SUB_DELIMS).add(b':'));
+
+/// Percent-encoding of `application/x-www-form-urlencoded` names and values.
+///
+/// Leaves `A-Za-z0-9*-._` unescaped and encodes space as `+`.
+#[allow(non_upper_case_globals)]
+pub static PercentForm: Percent = Percent::new(AsciiSet::ALPHANUMERIC.union(AsciiSet::new(b"*-._"))).space_as_plus();
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], percent: &Percent, mut buffer: B) -> B::Output {
+	let dest_len = Percent::estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, percent, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+unsafe fn encode_bytes(bytes: &[u8], percent: &Percent, mut dest: *mut u8) -> *mut u8 {
+	for &byte in bytes {
+		if percent.set.contains(byte) {
+			*dest = byte;
+			dest = dest.add(1);
+		}
+		else if percent.plus && byte == b' ' {
+			*dest = b'+';
+			dest = dest.add(1);
+		}
+		else {
+			*dest = b'%';
+			*dest.add(1) = encode::encode_char(byte >> 4, b'A');
+			*dest.add(2) = encode::encode_char(byte & 0xf, b'A');
+			dest = dest.add(3);
+		}
+	}
+
+	return dest;
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], percent: &Percent, mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = Percent::estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, percent, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+unsafe fn decode_string(string: &[u8], percent: &Percent, mut dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut i = 0;
+	while i < string.len() {
+		let byte = match string[i] {
+			b'%' => {
+				if i + 3 > string.len() {
+					// Report an invalid character before the missing one
+					if i + 2 == string.len() {
+						decode::decode_nibble(string, i + 1)?;
+					}
+					return Err(Error::IncorrectLength);
+				}
+				let byte = decode::decode_nibble(string, i + 1)? << 4 | decode::decode_nibble(string, i + 2)?;
+				i += 3;
+				byte
+			},
+			b'+' if percent.plus => {
+				i += 1;
+				b' '
+			},
+			byte => {
+				i += 1;
+				byte
+			},
+		};
+		*dest = byte;
+		dest = dest.add(1);
+	}
+
+	Ok(dest)
+}
@@ -0,0 +1,287 @@
+use super::*;
+
+/// Maximum length of an encoded line, excluding the line ending.
+const LINE_WIDTH: usize = 76;
+
+/// Quoted-printable encoding as specified by RFC 2045.
+///
+/// Printable ASCII characters other than `=` are left unescaped, all other bytes are escaped as `=XX` with upper-case hex digits.
+/// Space and tab are escaped at the end of a line to survive transports stripping trailing whitespace.
+/// Lines longer than 76 characters are split with soft line breaks `=` followed by the line ending.
+///
+/// In text mode the line breaks of the input, `\r\n` or `\n`, are encoded as hard line breaks with the configured line ending.
+/// In binary mode every `\r` and `\n` is escaped.
+///
+/// Escapes and soft line breaks make the output length depend on the content, size buffers with [`QuotedPrintable::estimate_encoded_len`].
+///
+/// Decoding accepts both `\r\n` and `\n` line breaks, removes soft line breaks and trailing whitespace and leaves hard line breaks as they are.
+/// Upper- and lower-case hex digits are accepted, all other characters are passed through.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::{LineEnding, QuotedPrintable};
+///
+/// let qp = QuotedPrintable::new(LineEnding::CrLf);
+///
+/// let encoded = qp.encode("Grüße, 1+1=2 \n".as_bytes());
+/// assert_eq!(encoded, "Gr=C3=BC=C3=9Fe, 1+1=3D2=20\r\n");
+///
+/// let decoded = qp.decode(&encoded).unwrap();
+/// assert_eq!(decoded, "Grüße, 1+1=2 \r\n".as_bytes());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct QuotedPrintable {
+	ending: LineEnding,
+	binary: bool,
+}
+
+impl QuotedPrintable {
+	/// Creates a new quoted-printable encoding in text mode with the line ending.
+	#[inline]
+	pub const fn new(ending: LineEnding) -> QuotedPrintable {
+		QuotedPrintable { ending, binary: false }
+	}
+
+	/// In binary mode.
+	///
+	/// Line breaks of the input are escaped instead of encoded as hard line breaks.
+	#[inline]
+	pub const fn binary(&self) -> QuotedPrintable {
+		QuotedPrintable { ending: self.ending, binary: true }
+	}
+
+	/// Estimates the maximum length of the encoded string given the length of the input bytes.
+	///
+	/// # Panics
+	///
+	/// Panics if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_encoded_len(len: usize) -> usize {
+		// Every line holds at least 73 characters before a soft line break of up to 3 characters
+		let Some(len) = len.checked_mul(3) else { ratio::panic_overflow() };
+		match len.checked_add(len / (LINE_WIDTH - 3) * 3) {
+			Some(len) => len,
+			None => ratio::panic_overflow(),
+		}
+	}
+
+	/// Estimates the maximum length of the decoded bytes given the length of the input string.
+	#[inline]
+	pub const fn estimate_decoded_len(len: usize) -> usize {
+		len
+	}
+
+	/// Encodes the input bytes.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode(bytes, self, std::string::String::new())
+	}
+
+	/// Decodes the input string.
+	///
+	/// Returns [`Error::InvalidCharacter`] for an escape with invalid hex digits and [`Error::IncorrectLength`] if the string ends in an incomplete escape.
+	#[cfg(feature = "std")]
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode(string.as_bytes(), std::vec::Vec::new())
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// The buffer must have room for [`QuotedPrintable::estimate_encoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::{LineEnding, QuotedPrintable};
+	///
+	/// let mut stack_buf = [0u8; 32];
+	/// let encoded = QuotedPrintable::new(LineEnding::Lf).binary().encode_into(b"a\tb\r\n", &mut stack_buf);
+	/// assert_eq!(encoded, "a\tb=0D=0A");
+	/// ```
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(bytes, self, buffer)
+	}
+
+	/// Decodes into a buffer.
+	///
+	/// The buffer must have room for [`QuotedPrintable::estimate_decoded_len`] bytes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::{LineEnding, QuotedPrintable};
+	///
+	/// let qp = QuotedPrintable::new(LineEnding::CrLf);
+	///
+	/// let mut stack_buf = [0u8; 32];
+	/// let decoded = qp.decode_into("soft =  \nline=\r\nbreak=3d", &mut stack_buf).unwrap();
+	/// assert_eq!(decoded, b"soft linebreak=");
+	///
+	/// assert_eq!(qp.decode("=4"), Err(basenc::Error::IncorrectLength));
+	/// assert_eq!(qp.decode("=4\n"), Err(basenc::Error::InvalidCharacter { offset: 2, byte: b'\n' }));
+	/// ```
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode(string.as_bytes(), buffer)
+	}
+}
+
+//----------------------------------------------------------------
+// Encoding
+
+#[inline(never)]
+fn encode<B: EncodeBuf>(bytes: &[u8], qp: &QuotedPrintable, mut buffer: B) -> B::Output {
+	let dest_len = QuotedPrintable::estimate_encoded_len(bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = encode_bytes(bytes, qp, dest);
+		let len = end.offset_from(dest) as usize;
+		buffer.commit(len)
+	}
+}
+
+// Returns the length of the hard line break at the start of the bytes
+#[inline]
+fn line_break(bytes: &[u8], qp: &QuotedPrintable) -> usize {
+	match bytes {
+		_ if qp.binary => 0,
+		[b'\n', ..] => 1,
+		[b'\r', b'\n', ..] => 2,
+		_ => 0,
+	}
+}
+
+#[inline]
+unsafe fn write_ending(ending: &[u8], dest: *mut u8) -> *mut u8 {
+	ptr::copy_nonoverlapping(ending.as_ptr(), dest, ending.len());
+	dest.add(ending.len())
+}
+
+unsafe fn encode_bytes(bytes: &[u8], qp: &QuotedPrintable, mut dest: *mut u8) -> *mut u8 {
+	let ending = qp.ending.as_str().as_bytes();
+	let mut column = 0;
+	let mut i = 0;
+	while i < bytes.len() {
+		let brk = line_break(&bytes[i..], qp);
+		if brk > 0 {
+			dest = write_ending(ending, dest);
+			column = 0;
+			i += brk;
+			continue;
+		}
+
+		let byte = bytes[i];
+		let eol = i + 1 == bytes.len() || line_break(&bytes[i + 1..], qp) > 0;
+		let literal = match byte {
+			b' ' | b'\t' => !eol,
+			b'=' => false,
+			b'!'..=b'~' => true,
+			_ => false,
+		};
+		let len = if literal { 1 } else { 3 };
+
+		// The last character of a line does not need room for the soft line break
+		let width = if eol { LINE_WIDTH } else { LINE_WIDTH - 1 };
+		if column + len > width {
+			*dest = b'=';
+			dest = write_ending(ending, dest.add(1));
+			column = 0;
+		}
+
+		if literal {
+			*dest = byte;
+		}
+		else {
+			*dest = b'=';
+			*dest.add(1) = encode::encode_char(byte >> 4, b'A');
+			*dest.add(2) = encode::encode_char(byte & 0xf, b'A');
+		}
+		dest = dest.add(len);
+		column += len;
+		i += 1;
+	}
+
+	return dest;
+}
+
+//----------------------------------------------------------------
+// Decoding
+
+#[inline(never)]
+fn decode<B: DecodeBuf>(string: &[u8], mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = QuotedPrintable::estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let end = decode_string(string, dest)?;
+		let len = end.offset_from(dest) as usize;
+		Ok(buffer.commit(len))
+	}
+}
+
+unsafe fn decode_string(string: &[u8], mut dest: *mut u8) -> Result<*mut u8, Error> {
+	let mut start = 0;
+	while start < string.len() {
+		let line_end = match string[start..].iter().position(|&chr| chr == b'\n') {
+			Some(pos) => start + pos + 1,
+			None => string.len(),
+		};
+
+		// Split the line break and trailing whitespace from the line
+		let mut end = line_end;
+		if end > start && string[end - 1] == b'\n' {
+			end -= 1;
+			if end > start && string[end - 1] == b'\r' {
+				end -= 1;
+			}
+		}
+		let brk = end;
+		while end > start && matches!(string[end - 1], b' ' | b'\t') {
+			end -= 1;
+		}
+
+		let mut i = start;
+		let mut soft = false;
+		while i < end {
+			let byte = match string[i] {
+				b'=' if i + 1 == end => {
+					soft = true;
+					break;
+				},
+				b'=' => {
+					// Escapes cannot span lines, the digits are checked against the original string
+					if i + 3 > string.len() {
+						if i + 2 == string.len() {
+							decode::decode_nibble(string, i + 1)?;
+						}
+						return Err(Error::IncorrectLength);
+					}
+					let byte = decode::decode_nibble(string, i + 1)? << 4 | decode::decode_nibble(string, i + 2)?;
+					i += 3;
+					byte
+				},
+				byte => {
+					i += 1;
+					byte
+				},
+			};
+			*dest = byte;
+			dest = dest.add(1);
+		}
+
+		if !soft {
+			let len = line_end - brk;
+			ptr::copy_nonoverlapping(string.as_ptr().add(brk), dest, len);
+			dest = dest.add(len);
+		}
+
+		start = line_end;
+	}
+
+	Ok(dest)
+}
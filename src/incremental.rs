@@ -2,6 +2,31 @@
 Incremental processing
 ======================
 
+Incremental encoder and decoder
+-------------------------------
+
+[`Encoder`] and [`Decoder`] accept input in arbitrarily sized pieces and keep the incomplete chunk between updates.
+They work with any [`EncodeBuf`] and [`DecodeBuf`] and are available without the `std` feature.
+
+```
+let mut encoder = basenc::incremental::Encoder::new(&basenc::LowerHex);
+let mut stack_buf = [0u8; 16];
+assert_eq!(encoder.update(b"\x12\x34", &mut stack_buf), "1234");
+assert_eq!(encoder.finish(&mut stack_buf), "");
+```
+
+Streaming adapters
+------------------
+
+With the `std` feature enabled, [`EncoderWriter`] encodes bytes written to it and passes the encoded string on to an inner writer.
+Incomplete chunks are buffered across writes, no alignment is required of the caller.
+
+[`DecoderReader`] reads encoded text from an inner reader and returns the decoded bytes.
+Incomplete chunks are carried over between reads and errors are reported as [`std::io::Error`].
+
+Manual chunking
+---------------
+
 The [`RATIO`](Encoding::RATIO) associated constant provides information about the encoding ratio and can be used to compute the chunk size for incremental processing.
 
 Encoding:
@@ -42,3 +67,46 @@ fn decode<E: basenc::Encoding>(encoding: &E, string: &str) {
 
 #[allow(unused_imports)]
 use super::*;
+
+pub(crate) mod encoder;
+pub use self::encoder::Encoder;
+
+pub(crate) mod decoder;
+pub use self::decoder::Decoder;
+
+/// State kept between updates by the incremental [`Encoder`] and [`Decoder`].
+///
+/// Encodings without fixed chunks, such as [`Base91`], keep their pending bits here.
+#[doc(hidden)]
+#[derive(Clone, Debug)]
+pub struct State {
+	pub(crate) pending: Scrub<[u8; u8::MAX as usize]>,
+	pub(crate) pending_len: usize,
+	// Offset of the pending characters in the input
+	pub(crate) offset: usize,
+	// Pending bits of bitstream encodings, least significant bit first
+	pub(crate) bits: Scrub<u32>,
+	pub(crate) nbits: u32,
+}
+
+impl State {
+	pub(crate) const fn new() -> State {
+		State {
+			pending: Scrub([0; u8::MAX as usize]),
+			pending_len: 0,
+			offset: 0,
+			bits: Scrub(0),
+			nbits: 0,
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+mod writer;
+#[cfg(feature = "std")]
+pub use self::writer::EncoderWriter;
+
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+pub use self::reader::DecoderReader;
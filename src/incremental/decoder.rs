@@ -0,0 +1,124 @@
+use super::*;
+
+/// Incremental decoder.
+///
+/// Strings passed to [`update`](Self::update) are decoded in complete chunks.
+/// The remaining characters are kept until the next update or until [`finish`](Self::finish) decodes them.
+///
+/// The last chunk is always held back until [`finish`](Self::finish) is called.
+/// Padding, [`Error::NonCanonical`] and [`Error::IncorrectLength`] are only checked at the end of the input.
+/// Padding followed by more input is rejected if the encoding rejects it in a single string, eg. in [`Mode::Strict`].
+///
+/// Error offsets are relative to the start of the whole input, not the current update.
+///
+/// # Examples
+///
+/// ```
+/// let mut decoder = basenc::incremental::Decoder::new(&basenc::Base64Std);
+/// let mut stack_buf = [0u8; 16];
+/// let mut bytes = Vec::new();
+/// bytes.extend_from_slice(decoder.update(b"aGVsbG8g", &mut stack_buf).unwrap());
+/// bytes.extend_from_slice(decoder.update(b"d29ybGQ=", &mut stack_buf).unwrap());
+/// bytes.extend_from_slice(decoder.finish(&mut stack_buf).unwrap());
+/// assert_eq!(bytes, b"hello world");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Decoder<'a, E> {
+	encoding: &'a E,
+	state: State,
+}
+
+impl<'a, E: Encoding> Decoder<'a, E> {
+	/// Constructor.
+	#[inline]
+	pub const fn new(encoding: &'a E) -> Self {
+		Decoder { encoding, state: State::new() }
+	}
+
+	/// Decodes the string into a buffer.
+	///
+	/// The buffer must have room for [`estimate_decoded_len`](Ratio::estimate_decoded_len) of the input length plus one chunk.
+	#[inline]
+	pub fn update<B: DecodeBuf>(&mut self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		self.encoding.decode_update(&mut self.state, string, buffer)
+	}
+
+	/// Decodes the remaining characters into a buffer.
+	#[inline]
+	pub fn finish<B: DecodeBuf>(mut self, buffer: B) -> Result<B::Output, Error> {
+		self.encoding.decode_finish(&mut self.state, buffer)
+	}
+}
+
+// Decodes the string in complete chunks, see Encoding::decode_update
+pub(crate) fn update<E: Encoding + ?Sized, B: DecodeBuf>(encoding: &E, state: &mut State, mut string: &[u8], mut buffer: B) -> Result<B::Output, Error> {
+	let quantum = E::RATIO.encoded as usize;
+
+	// Top up the pending characters to a complete quantum
+	if state.pending_len > 0 {
+		let len = cmp::min(quantum - state.pending_len, string.len());
+		state.pending[state.pending_len..state.pending_len + len].copy_from_slice(&string[..len]);
+		state.pending_len += len;
+		string = &string[len..];
+	}
+
+	// The pending quantum is only decoded if more input follows
+	let head = state.pending_len == quantum && !string.is_empty();
+
+	// Hold back the last quantum, complete or not
+	let (bulk, tail) = string.split_at(string.len().saturating_sub(1) / quantum * quantum);
+
+	let head_len = if head { E::RATIO.decoded as usize } else { 0 };
+	let dest_len = head_len + E::RATIO.estimate_decoded_len(bulk.len());
+
+	let output = unsafe {
+		let dest = buffer.allocate(dest_len);
+		let dest = slice::from_raw_parts_mut(dest as *mut mem::MaybeUninit<u8>, dest_len);
+		let mut len = 0;
+		if head {
+			let decoded = encoding.decode_into(&state.pending[..quantum], &mut dest[..])
+				.map_err(|err| err.offset_by(state.offset))?.len();
+			if decoded < head_len {
+				check_padded(encoding, &state.pending[..quantum], string)
+					.map_err(|err| err.offset_by(state.offset))?;
+			}
+			len += decoded;
+			state.offset += quantum;
+		}
+		if !bulk.is_empty() {
+			let decoded = encoding.decode_into(bulk, &mut dest[len..])
+				.map_err(|err| err.offset_by(state.offset))?.len();
+			if decoded < bulk.len() / quantum * E::RATIO.decoded as usize {
+				check_padded(encoding, &bulk[bulk.len() - quantum..], tail)
+					.map_err(|err| err.offset_by(state.offset + bulk.len() - quantum))?;
+			}
+			len += decoded;
+			state.offset += bulk.len();
+		}
+		buffer.commit(len)
+	};
+
+	if !tail.is_empty() {
+		state.pending[..tail.len()].copy_from_slice(tail);
+		state.pending_len = tail.len();
+	}
+
+	Ok(output)
+}
+
+// Decoding the chunks separately accepts padding followed by more input.
+// Decodes the short quantum together with the next characters and lets the encoding decide.
+#[cold]
+fn check_padded<E: Encoding + ?Sized>(encoding: &E, quantum: &[u8], next: &[u8]) -> Result<(), Error> {
+	let mut string = Scrub([0u8; 2 * u8::MAX as usize]);
+	let len = quantum.len() + cmp::min(next.len(), quantum.len());
+	string[..quantum.len()].copy_from_slice(quantum);
+	string[quantum.len()..len].copy_from_slice(&next[..len - quantum.len()]);
+
+	// Errors in the next characters are reported when they are decoded
+	let mut stack_buf = Scrub([0u8; 2 * u8::MAX as usize]);
+	match encoding.decode_into(&string[..len], &mut stack_buf[..]) {
+		Err(err) if err.offset().is_some_and(|offset| offset < quantum.len()) => Err(err),
+		_ => Ok(()),
+	}
+}
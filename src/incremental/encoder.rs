@@ -0,0 +1,88 @@
+use super::*;
+
+/// Incremental encoder.
+///
+/// Bytes passed to [`update`](Self::update) are encoded in complete chunks.
+/// The remaining bytes are kept until the next update or until [`finish`](Self::finish) encodes them with padding.
+///
+/// # Examples
+///
+/// ```
+/// let mut encoder = basenc::incremental::Encoder::new(&basenc::Base64Std);
+/// let mut string = String::new();
+/// encoder.update(b"hello ", &mut string);
+/// encoder.update(b"world", &mut string);
+/// encoder.finish(&mut string);
+/// assert_eq!(string, "aGVsbG8gd29ybGQ");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Encoder<'a, E> {
+	pub(super) encoding: &'a E,
+	state: State,
+}
+
+impl<'a, E: Encoding> Encoder<'a, E> {
+	/// Constructor.
+	#[inline]
+	pub const fn new(encoding: &'a E) -> Self {
+		Encoder { encoding, state: State::new() }
+	}
+
+	/// Encodes the bytes into a buffer.
+	///
+	/// The buffer must have room for [`estimate_encoded_len`](Ratio::estimate_encoded_len) of the input length plus one chunk.
+	#[inline]
+	pub fn update<B: EncodeBuf>(&mut self, bytes: &[u8], buffer: B) -> B::Output {
+		self.encoding.encode_update(&mut self.state, bytes, buffer)
+	}
+
+	/// Encodes the remaining bytes into a buffer.
+	///
+	/// The final chunk is padded according to the encoding's padding policy.
+	#[inline]
+	pub fn finish<B: EncodeBuf>(mut self, buffer: B) -> B::Output {
+		self.encoding.encode_finish(&mut self.state, buffer)
+	}
+}
+
+// Encodes the bytes in complete chunks, see Encoding::encode_update
+pub(crate) fn update<E: Encoding + ?Sized, B: EncodeBuf>(encoding: &E, state: &mut State, mut bytes: &[u8], mut buffer: B) -> B::Output {
+	let quantum = E::RATIO.decoded as usize;
+
+	// Top up the pending bytes to a complete quantum
+	let mut head = false;
+	if state.pending_len > 0 {
+		let len = cmp::min(quantum - state.pending_len, bytes.len());
+		state.pending[state.pending_len..state.pending_len + len].copy_from_slice(&bytes[..len]);
+		state.pending_len += len;
+		bytes = &bytes[len..];
+		head = state.pending_len == quantum;
+	}
+
+	// Keep the incomplete quantum for later
+	let (bulk, tail) = bytes.split_at(bytes.len() - bytes.len() % quantum);
+
+	let head_len = if head { E::RATIO.encoded as usize } else { 0 };
+	let dest_len = head_len + E::RATIO.estimate_encoded_len(bulk.len());
+
+	let output = unsafe {
+		let dest = buffer.allocate(dest_len);
+		let dest = slice::from_raw_parts_mut(dest as *mut mem::MaybeUninit<u8>, dest_len);
+		let mut len = 0;
+		if head {
+			len += encoding.encode_into(&state.pending[..quantum], &mut dest[..]).len();
+		}
+		len += encoding.encode_into(bulk, &mut dest[len..]).len();
+		buffer.commit(len)
+	};
+
+	if head {
+		state.pending_len = 0;
+	}
+	if !tail.is_empty() {
+		state.pending[..tail.len()].copy_from_slice(tail);
+		state.pending_len = tail.len();
+	}
+
+	output
+}
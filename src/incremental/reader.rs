@@ -0,0 +1,119 @@
+use std::io;
+use super::*;
+
+/// Decoder wrapping a reader.
+///
+/// Encoded text is read from the inner reader and decoded bytes are returned.
+/// The input does not need to be aligned to the encoding's chunk size, incomplete chunks are carried over between reads.
+///
+/// Errors are reported as [`io::ErrorKind::InvalidData`] wrapping the decoding [`Error`].
+/// Padding, [`Error::NonCanonical`] and [`Error::IncorrectLength`] are only checked at the end of the stream.
+/// Padding followed by more input is rejected if the encoding rejects it in a single string, eg. in [`Mode::Strict`].
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+///
+/// let mut reader = basenc::incremental::DecoderReader::new(&basenc::Base64Std, &b"aGVsbG8gd29ybGQ="[..]);
+/// let mut decoded = Vec::new();
+/// reader.read_to_end(&mut decoded).unwrap();
+/// assert_eq!(decoded, b"hello world");
+/// ```
+pub struct DecoderReader<'a, E: Encoding, R: io::Read> {
+	decoder: Option<Decoder<'a, E>>,
+	reader: R,
+	input: Scrub<[u8; 512]>,
+	output: Scrub<[u8; 512]>,
+	output_start: usize,
+	output_end: usize,
+}
+
+impl<'a, E: Encoding, R: io::Read> DecoderReader<'a, E, R> {
+	/// Wraps the reader.
+	#[inline]
+	pub fn new(encoding: &'a E, reader: R) -> Self {
+		DecoderReader {
+			decoder: Some(Decoder::new(encoding)),
+			reader,
+			input: Scrub([0; 512]),
+			output: Scrub([0; 512]),
+			output_start: 0,
+			output_end: 0,
+		}
+	}
+
+	/// Gets a reference to the inner reader.
+	#[inline]
+	pub fn get_ref(&self) -> &R {
+		&self.reader
+	}
+
+	/// Gets a mutable reference to the inner reader.
+	///
+	/// Reading directly from the inner reader may corrupt the decoded output.
+	#[inline]
+	pub fn get_mut(&mut self) -> &mut R {
+		&mut self.reader
+	}
+
+	/// Returns the inner reader.
+	///
+	/// Any buffered input is lost.
+	#[inline]
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
+
+	// Decodes the next chunk into the output buffer.
+	// Returns false if the end of the stream has been reached.
+	fn fill(&mut self) -> io::Result<bool> {
+		let Some(decoder) = &mut self.decoder
+		else {
+			return Ok(false);
+		};
+
+		// Leave room for the pending quantum
+		let chunk_size = E::RATIO.decoding_chunk_size(self.output.len()) - E::RATIO.encoded as usize;
+		let chunk_size = cmp::min(chunk_size, self.input.len());
+
+		let len = loop {
+			match self.reader.read(&mut self.input[..chunk_size]) {
+				Ok(len) => break len,
+				Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+				Err(err) => return Err(err),
+			}
+		};
+
+		let result = if len == 0 {
+			self.decoder.take().unwrap().finish(&mut self.output[..])
+		}
+		else {
+			decoder.update(&self.input[..len], &mut self.output[..])
+		};
+
+		let decoded = result.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+		self.output_start = 0;
+		self.output_end = decoded.len();
+		Ok(true)
+	}
+}
+
+impl<'a, E: Encoding, R: io::Read> io::Read for DecoderReader<'a, E, R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		if buf.is_empty() {
+			return Ok(0);
+		}
+
+		while self.output_start == self.output_end {
+			if !self.fill()? {
+				return Ok(0);
+			}
+		}
+
+		let len = cmp::min(buf.len(), self.output_end - self.output_start);
+		buf[..len].copy_from_slice(&self.output[self.output_start..self.output_start + len]);
+		self.output_start += len;
+		Ok(len)
+	}
+}
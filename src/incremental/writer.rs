@@ -0,0 +1,94 @@
+use std::io;
+use super::*;
+
+/// Encoder wrapping a writer.
+///
+/// Bytes written are encoded and passed on to the inner writer.
+/// Bytes which do not fill a complete chunk are buffered until more bytes are written or the encoder is finished.
+///
+/// Call [`finish`](Self::finish) to write the final chunk with padding and recover the inner writer.
+/// Dropping the encoder finishes it on a best effort basis, ignoring any errors.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// let mut writer = basenc::incremental::EncoderWriter::new(&basenc::Base64Std, Vec::new());
+/// writer.write_all(b"hello ").unwrap();
+/// writer.write_all(b"world").unwrap();
+/// let encoded = writer.finish().unwrap();
+/// assert_eq!(encoded, b"aGVsbG8gd29ybGQ");
+/// ```
+pub struct EncoderWriter<'a, E: Encoding, W: io::Write> {
+	encoder: Encoder<'a, E>,
+	writer: Option<W>,
+}
+
+impl<'a, E: Encoding, W: io::Write> EncoderWriter<'a, E, W> {
+	/// Wraps the writer.
+	#[inline]
+	pub fn new(encoding: &'a E, writer: W) -> Self {
+		EncoderWriter {
+			encoder: Encoder::new(encoding),
+			writer: Some(writer),
+		}
+	}
+
+	/// Gets a reference to the inner writer.
+	#[inline]
+	pub fn get_ref(&self) -> &W {
+		self.writer.as_ref().unwrap()
+	}
+
+	/// Gets a mutable reference to the inner writer.
+	///
+	/// Writing directly to the inner writer may corrupt the encoded output.
+	#[inline]
+	pub fn get_mut(&mut self) -> &mut W {
+		self.writer.as_mut().unwrap()
+	}
+
+	/// Encodes the remaining bytes and returns the inner writer.
+	///
+	/// The final chunk is padded according to the encoding's padding policy.
+	pub fn finish(mut self) -> io::Result<W> {
+		self.write_pending()?;
+		let mut writer = self.writer.take().unwrap();
+		writer.flush()?;
+		Ok(writer)
+	}
+
+	fn write_pending(&mut self) -> io::Result<()> {
+		let encoding = self.encoder.encoding;
+		let encoder = mem::replace(&mut self.encoder, Encoder::new(encoding));
+		let mut stack_buf = Scrub(mem::MaybeUninit::<[u8; 512]>::uninit());
+		let string = encoder.finish(&mut *stack_buf);
+		self.writer.as_mut().unwrap().write_all(string.as_bytes())
+	}
+}
+
+impl<'a, E: Encoding, W: io::Write> io::Write for EncoderWriter<'a, E, W> {
+	fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+		let mut stack_buf = Scrub(mem::MaybeUninit::<[u8; 512]>::uninit());
+		// Leave room for the pending quantum
+		let chunk_size = E::RATIO.encoding_chunk_size(mem::size_of_val(&stack_buf)) - E::RATIO.decoded as usize;
+
+		let len = cmp::min(bytes.len(), chunk_size);
+		let string = self.encoder.update(&bytes[..len], &mut *stack_buf);
+		self.writer.as_mut().unwrap().write_all(string.as_bytes())?;
+		Ok(len)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.writer.as_mut().unwrap().flush()
+	}
+}
+
+impl<'a, E: Encoding, W: io::Write> Drop for EncoderWriter<'a, E, W> {
+	fn drop(&mut self) {
+		if self.writer.is_some() {
+			let _ = self.write_pending();
+		}
+	}
+}
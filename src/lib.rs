@@ -40,7 +40,7 @@ Existing buffers can be reused with the [`encode_into`](Encoding::encode_into) a
 #![no_std]
 
 #[allow(unused_imports)]
-use core::{fmt, mem, ptr, slice, str};
+use core::{cmp, fmt, mem, ptr, slice, str};
 
 #[cfg(any(test, feature = "std"))]
 #[macro_use]
@@ -62,6 +62,19 @@ pub use Padding::None as NoPad;
 mod buf;
 pub use self::buf::*;
 
+mod sha256;
+
+mod zeroize;
+use self::zeroize::Scrub;
+#[cfg(all(feature = "zeroize", feature = "std"))]
+pub use self::zeroize::Zeroizing;
+
+mod whitespace;
+pub use self::whitespace::SkipWhitespace;
+
+mod wrap;
+pub use self::wrap::{LineEnding, Wrap};
+
 mod hex;
 pub use self::hex::*;
 
@@ -71,8 +84,36 @@ pub use self::base64::*;
 mod base32;
 pub use self::base32::*;
 
+mod base45;
+pub use self::base45::*;
+
+mod base58;
+pub use self::base58::*;
+
+mod ascii85;
+pub use self::ascii85::*;
+
+mod base85;
+pub use self::base85::*;
+
+mod z85;
+pub use self::z85::*;
+
+mod base91;
+pub use self::base91::*;
+
 pub mod incremental;
 
+pub mod ct;
+
+pub mod multibase;
+
+pub mod encoded_word;
+
+pub mod data_url;
+
+pub mod pem;
+
 //----------------------------------------------------------------
 
 /// Decoding error.
@@ -82,22 +123,66 @@ pub mod incremental;
 #[non_exhaustive]
 pub enum Error {
 	/// Not a valid character in the alphabet.
-	InvalidCharacter,
+	InvalidCharacter {
+		/// Offset of the character in the input.
+		offset: usize,
+		/// The invalid character.
+		byte: u8,
+	},
 	/// Input has incorrect length or is not padded to the required length.
 	IncorrectLength,
 	/// Input is not canonical.
 	///
 	/// Unused padding MUST consist of zero bits.
-	NonCanonical,
+	NonCanonical {
+		/// Offset of the final quantum in the input.
+		offset: usize,
+	},
+	/// The checksum does not match the decoded data.
+	ChecksumMismatch,
+	/// The value of a group is out of range.
+	OutOfRange {
+		/// Offset of the group in the input.
+		offset: usize,
+	},
+}
+
+impl Error {
+	/// Returns the offset in the input where the error was detected, if known.
+	#[inline]
+	pub const fn offset(&self) -> Option<usize> {
+		match *self {
+			Error::InvalidCharacter { offset, .. } => Some(offset),
+			Error::IncorrectLength => None,
+			Error::NonCanonical { offset } => Some(offset),
+			Error::ChecksumMismatch => None,
+			Error::OutOfRange { offset } => Some(offset),
+		}
+	}
+
+	/// Moves the offset by the position of the chunk in the input.
+	#[inline]
+	pub(crate) const fn offset_by(self, position: usize) -> Error {
+		match self {
+			Error::InvalidCharacter { offset, byte } => Error::InvalidCharacter { offset: offset + position, byte },
+			Error::IncorrectLength => Error::IncorrectLength,
+			Error::NonCanonical { offset } => Error::NonCanonical { offset: offset + position },
+			Error::ChecksumMismatch => Error::ChecksumMismatch,
+			Error::OutOfRange { offset } => Error::OutOfRange { offset: offset + position },
+		}
+	}
 }
 
 impl fmt::Display for Error {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str(match self {
-			Error::InvalidCharacter => "invalid character",
-			Error::IncorrectLength => "incorrect length",
-			Error::NonCanonical => "non-canonical input",
-		})
+		match *self {
+			Error::InvalidCharacter { offset, byte } if byte.is_ascii_graphic() => write!(f, "invalid character '{}' at offset {}", byte as char, offset),
+			Error::InvalidCharacter { offset, byte } => write!(f, "invalid character 0x{:02x} at offset {}", byte, offset),
+			Error::IncorrectLength => f.write_str("incorrect length"),
+			Error::NonCanonical { offset } => write!(f, "non-canonical input at offset {}", offset),
+			Error::ChecksumMismatch => f.write_str("checksum mismatch"),
+			Error::OutOfRange { offset } => write!(f, "value out of range at offset {}", offset),
+		}
 	}
 }
 
@@ -123,15 +208,15 @@ impl<'a, E: Encoding> Display<'a, E> {
 
 impl<'a, E: Encoding> fmt::Display for Display<'a, E> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		let mut stack_buf = mem::MaybeUninit::<[u8; 512]>::uninit();
-		let chunk_size = E::RATIO.encoding_chunk_size(mem::size_of_val(&stack_buf));
+		let mut stack_buf = Scrub(mem::MaybeUninit::<[u8; 512]>::uninit());
+		// Leave room for the pending quantum
+		let chunk_size = E::RATIO.encoding_chunk_size(mem::size_of_val(&stack_buf)) - E::RATIO.decoded as usize;
 
+		let mut encoder = incremental::Encoder::new(self.encoding);
 		for chunk in self.bytes.chunks(chunk_size) {
-			let string = self.encoding.encode_into(chunk, &mut stack_buf);
-			f.write_str(string)?;
+			f.write_str(encoder.update(chunk, &mut *stack_buf))?;
 		}
-
-		Ok(())
+		f.write_str(encoder.finish(&mut *stack_buf))
 	}
 }
 
@@ -147,4 +232,35 @@ pub trait Encoding {
 
 	/// Decodes into a decoding buffer.
 	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error>;
+
+	/// Encodes the next piece of input for the [`incremental::Encoder`].
+	///
+	/// Splits the input in complete chunks by default, encodings without fixed chunks keep their own state.
+	#[doc(hidden)]
+	#[inline]
+	fn encode_update<B: EncodeBuf>(&self, state: &mut incremental::State, bytes: &[u8], buffer: B) -> B::Output {
+		incremental::encoder::update(self, state, bytes, buffer)
+	}
+
+	/// Encodes the remaining input for the [`incremental::Encoder`].
+	#[doc(hidden)]
+	#[inline]
+	fn encode_finish<B: EncodeBuf>(&self, state: &mut incremental::State, buffer: B) -> B::Output {
+		self.encode_into(&state.pending[..state.pending_len], buffer)
+	}
+
+	/// Decodes the next piece of input for the [`incremental::Decoder`].
+	#[doc(hidden)]
+	#[inline]
+	fn decode_update<B: DecodeBuf>(&self, state: &mut incremental::State, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		incremental::decoder::update(self, state, string, buffer)
+	}
+
+	/// Decodes the remaining input for the [`incremental::Decoder`].
+	#[doc(hidden)]
+	#[inline]
+	fn decode_finish<B: DecodeBuf>(&self, state: &mut incremental::State, buffer: B) -> Result<B::Output, Error> {
+		self.decode_into(&state.pending[..state.pending_len], buffer)
+			.map_err(|err| err.offset_by(state.offset))
+	}
 }
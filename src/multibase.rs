@@ -0,0 +1,274 @@
+/*!
+Multibase prefix-tagged encoding.
+=================================
+
+[Multibase](https://github.com/multiformats/multibase) prefixes the encoded string with a single character identifying its base.
+It is used by IPFS and libp2p identifiers.
+
+The supported bases are listed in [`Base`], decoding returns the base which was detected.
+
+Examples
+--------
+
+```
+use basenc::multibase::{self, Base};
+
+let encoded = multibase::encode(Base::Base32Lower, b"hello world");
+assert_eq!(encoded, "bnbswy3dpeb3w64tmmq");
+
+let (base, decoded) = multibase::decode(&encoded).unwrap();
+assert_eq!(base, Base::Base32Lower);
+assert_eq!(decoded, b"hello world");
+```
+*/
+
+use super::*;
+
+/// Multibase base.
+///
+/// Only the bases supported by this crate are listed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Base {
+	/// `f`: hex with lower-case letters.
+	Base16Lower,
+	/// `F`: hex with upper-case letters.
+	Base16Upper,
+	/// `b`: RFC 4648 base32 with lower-case letters, no padding.
+	Base32Lower,
+	/// `B`: RFC 4648 base32 with upper-case letters, no padding.
+	Base32Upper,
+	/// `c`: RFC 4648 base32 with lower-case letters and padding.
+	Base32PadLower,
+	/// `C`: RFC 4648 base32 with upper-case letters and padding.
+	Base32PadUpper,
+	/// `v`: RFC 4648 base32hex with lower-case letters, no padding.
+	Base32HexLower,
+	/// `V`: RFC 4648 base32hex with upper-case letters, no padding.
+	Base32HexUpper,
+	/// `t`: RFC 4648 base32hex with lower-case letters and padding.
+	Base32HexPadLower,
+	/// `T`: RFC 4648 base32hex with upper-case letters and padding.
+	Base32HexPadUpper,
+	/// `h`: z-base-32.
+	Base32Z,
+	/// `z`: base58 with the Bitcoin alphabet.
+	Base58Btc,
+	/// `Z`: base58 with the Flickr alphabet.
+	Base58Flickr,
+	/// `m`: RFC 4648 base64, no padding.
+	Base64,
+	/// `M`: RFC 4648 base64 with padding.
+	Base64Pad,
+	/// `u`: RFC 4648 base64url, no padding.
+	Base64Url,
+	/// `U`: RFC 4648 base64url with padding.
+	Base64UrlPad,
+}
+
+impl Base {
+	/// Returns the prefix character.
+	#[inline]
+	pub const fn code(self) -> u8 {
+		match self {
+			Base::Base16Lower => b'f',
+			Base::Base16Upper => b'F',
+			Base::Base32Lower => b'b',
+			Base::Base32Upper => b'B',
+			Base::Base32PadLower => b'c',
+			Base::Base32PadUpper => b'C',
+			Base::Base32HexLower => b'v',
+			Base::Base32HexUpper => b'V',
+			Base::Base32HexPadLower => b't',
+			Base::Base32HexPadUpper => b'T',
+			Base::Base32Z => b'h',
+			Base::Base58Btc => b'z',
+			Base::Base58Flickr => b'Z',
+			Base::Base64 => b'm',
+			Base::Base64Pad => b'M',
+			Base::Base64Url => b'u',
+			Base::Base64UrlPad => b'U',
+		}
+	}
+
+	/// Returns the base for the prefix character.
+	#[inline]
+	pub const fn from_code(code: u8) -> Option<Base> {
+		let base = match code {
+			b'f' => Base::Base16Lower,
+			b'F' => Base::Base16Upper,
+			b'b' => Base::Base32Lower,
+			b'B' => Base::Base32Upper,
+			b'c' => Base::Base32PadLower,
+			b'C' => Base::Base32PadUpper,
+			b'v' => Base::Base32HexLower,
+			b'V' => Base::Base32HexUpper,
+			b't' => Base::Base32HexPadLower,
+			b'T' => Base::Base32HexPadUpper,
+			b'h' => Base::Base32Z,
+			b'z' => Base::Base58Btc,
+			b'Z' => Base::Base58Flickr,
+			b'm' => Base::Base64,
+			b'M' => Base::Base64Pad,
+			b'u' => Base::Base64Url,
+			b'U' => Base::Base64UrlPad,
+			_ => return None,
+		};
+		Some(base)
+	}
+}
+
+//----------------------------------------------------------------
+
+static BASE32_LOWER: Base32 = Base32::new(b"abcdefghijklmnopqrstuvwxyz234567");
+static BASE32_HEX_LOWER: Base32 = Base32::new(b"0123456789abcdefghijklmnopqrstuv");
+
+// Evaluates the expression with the encoding of the base
+macro_rules! dispatch {
+	($base:expr, |$encoding:ident| $body:expr) => {
+		match $base {
+			Base::Base16Lower => { let $encoding = &LowerHex; $body }
+			Base::Base16Upper => { let $encoding = &UpperHex; $body }
+			Base::Base32Lower => { let $encoding = &BASE32_LOWER.pad(Padding::None); $body }
+			Base::Base32Upper => { let $encoding = &Base32Std.pad(Padding::None); $body }
+			Base::Base32PadLower => { let $encoding = &BASE32_LOWER.pad(Padding::Strict); $body }
+			Base::Base32PadUpper => { let $encoding = &Base32Std.pad(Padding::Strict); $body }
+			Base::Base32HexLower => { let $encoding = &BASE32_HEX_LOWER.pad(Padding::None); $body }
+			Base::Base32HexUpper => { let $encoding = &Base32Hex.pad(Padding::None); $body }
+			Base::Base32HexPadLower => { let $encoding = &BASE32_HEX_LOWER.pad(Padding::Strict); $body }
+			Base::Base32HexPadUpper => { let $encoding = &Base32Hex.pad(Padding::Strict); $body }
+			Base::Base32Z => { let $encoding = &Base32Z.pad(Padding::None); $body }
+			Base::Base58Btc => { let $encoding = &Base58Btc; $body }
+			Base::Base58Flickr => { let $encoding = &Base58Flickr; $body }
+			Base::Base64 => { let $encoding = &Base64Std.pad(Padding::None); $body }
+			Base::Base64Pad => { let $encoding = &Base64Std.pad(Padding::Strict); $body }
+			Base::Base64Url => { let $encoding = &Base64Url.pad(Padding::None); $body }
+			Base::Base64UrlPad => { let $encoding = &Base64Url.pad(Padding::Strict); $body }
+		}
+	};
+}
+
+/// Estimates the maximum length of the encoded string given the base and the length of the input bytes.
+///
+/// # Panics
+///
+/// Panics if the result overflows `usize`.
+#[inline]
+pub const fn estimate_encoded_len(base: Base, len: usize) -> usize {
+	let len = match base {
+		Base::Base16Lower | Base::Base16Upper => <LowerHex as Encoding>::RATIO.estimate_encoded_len(len),
+		Base::Base58Btc | Base::Base58Flickr => Base58::estimate_encoded_len(len),
+		Base::Base64 | Base::Base64Pad | Base::Base64Url | Base::Base64UrlPad => <Base64 as Encoding>::RATIO.estimate_encoded_len(len),
+		_ => <Base32 as Encoding>::RATIO.estimate_encoded_len(len),
+	};
+	match len.checked_add(1) {
+		Some(len) => len,
+		None => ratio::panic_overflow(),
+	}
+}
+
+/// Estimates the maximum length of the decoded bytes given the length of the input string.
+///
+/// The estimate is the maximum over all bases.
+///
+/// # Panics
+///
+/// Panics if the result overflows `usize`.
+#[inline]
+pub const fn estimate_decoded_len(len: usize) -> usize {
+	let mut max = 0;
+	let bases = [Base::Base16Lower, Base::Base32Lower, Base::Base58Btc, Base::Base64];
+	let mut i = 0;
+	while i < bases.len() {
+		let len = decoded_len(bases[i], len);
+		if len > max {
+			max = len;
+		}
+		i += 1;
+	}
+	max
+}
+
+// Estimates the maximum length of the decoded bytes given the base
+const fn decoded_len(base: Base, len: usize) -> usize {
+	match base {
+		Base::Base16Lower | Base::Base16Upper => <LowerHex as Encoding>::RATIO.estimate_decoded_len(len),
+		Base::Base58Btc | Base::Base58Flickr => Base58::estimate_decoded_len(len),
+		Base::Base64 | Base::Base64Pad | Base::Base64Url | Base::Base64UrlPad => <Base64 as Encoding>::RATIO.estimate_decoded_len(len),
+		_ => <Base32 as Encoding>::RATIO.estimate_decoded_len(len),
+	}
+}
+
+/// Encodes the input bytes with the prefix of the base.
+#[cfg(feature = "std")]
+#[inline]
+pub fn encode(base: Base, bytes: &[u8]) -> std::string::String {
+	encode_into(base, bytes, std::string::String::new())
+}
+
+/// Decodes the input string and returns the detected base.
+///
+/// Returns [`Error::IncorrectLength`] if the string is empty and [`Error::InvalidCharacter`] at offset 0 if the base is not supported.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::multibase::{self, Base};
+///
+/// let (base, decoded) = multibase::decode("MaGVsbG8gd29ybGQ=").unwrap();
+/// assert_eq!(base, Base::Base64Pad);
+/// assert_eq!(decoded, b"hello world");
+///
+/// assert_eq!(multibase::decode("k"), Err(basenc::Error::InvalidCharacter { offset: 0, byte: b'k' }));
+/// ```
+#[cfg(feature = "std")]
+#[inline]
+pub fn decode(string: &str) -> Result<(Base, std::vec::Vec<u8>), Error> {
+	decode_into(string, std::vec::Vec::new())
+}
+
+/// Encodes into a buffer with the prefix of the base.
+///
+/// The buffer must have room for [`estimate_encoded_len`] bytes.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::multibase::{self, Base};
+///
+/// let mut stack_buf = [0u8; multibase::estimate_encoded_len(Base::Base58Btc, 11)];
+/// let encoded = multibase::encode_into(Base::Base58Btc, b"hello world", &mut stack_buf);
+/// assert_eq!(encoded, "zStV1DL6CwTryKyV");
+/// ```
+#[inline(never)]
+pub fn encode_into<B: EncodeBuf>(base: Base, bytes: &[u8], mut buffer: B) -> B::Output {
+	let dest_len = estimate_encoded_len(base, bytes.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		*dest = base.code();
+		let tail = slice::from_raw_parts_mut(dest.add(1) as *mut mem::MaybeUninit<u8>, dest_len - 1);
+		let len = dispatch!(base, |encoding| encoding.encode_into(bytes, tail).len());
+		buffer.commit(1 + len)
+	}
+}
+
+/// Decodes into a buffer and returns the detected base.
+///
+/// The buffer must have room for [`estimate_decoded_len`] bytes.
+#[inline(never)]
+pub fn decode_into<B: DecodeBuf>(string: &str, mut buffer: B) -> Result<(Base, B::Output), Error> {
+	let &code = string.as_bytes().first().ok_or(Error::IncorrectLength)?;
+	let base = Base::from_code(code).ok_or(Error::InvalidCharacter { offset: 0, byte: code })?;
+	let string = &string[1..];
+
+	let dest_len = decoded_len(base, string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let tail = slice::from_raw_parts_mut(dest as *mut mem::MaybeUninit<u8>, dest_len);
+		let result = dispatch!(base, |encoding| encoding.decode_into(string, tail).map(|bytes| bytes.len()));
+		let len = result.map_err(|err| err.offset_by(1))?;
+		Ok((base, buffer.commit(len)))
+	}
+}
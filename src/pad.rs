@@ -14,18 +14,55 @@ pub enum Padding {
 	Strict,
 }
 
+/// Decoding mode.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+	/// Normal decoding.
+	///
+	/// Padded chunks are accepted anywhere in the input, concatenated padded segments such as `Zg==Zg==` decode.
+	#[default]
+	Normal,
+	/// Strict RFC 4648 decoding.
+	///
+	/// Padding is only accepted in the final chunk, padding followed by more input is an [`InvalidCharacter`](crate::Error::InvalidCharacter) error.
+	/// Together with [`Padding::Strict`] every byte sequence has exactly one accepted encoding.
+	Strict,
+	/// Lenient decoding.
+	///
+	/// Non-zero unused bits in the final chunk are ignored instead of rejected as [`NonCanonical`](crate::Error::NonCanonical).
+	/// Some older encoders produce such output, decoding it is lossy in that different strings decode to the same bytes.
+	Lenient,
+}
+
 /// Encoding with explicit padding policy.
 #[derive(Clone, Debug)]
 pub struct WithPad<'a, T> {
 	pub(crate) encoding: &'a T,
 	pub(crate) pad: Padding,
+	pub(crate) mode: Mode,
 }
 
 impl<'a, T> WithPad<'a, T> {
 	/// Constructor.
 	#[inline]
 	pub const fn new(encoding: &'a T, pad: Padding) -> Self {
-		WithPad { encoding, pad }
+		WithPad { encoding, pad, mode: Mode::Normal }
+	}
+
+	/// With explicit decoding mode.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use basenc::{Mode, Padding};
+	///
+	/// let encoding = basenc::Base64Std.pad(Padding::Strict);
+	/// assert!(encoding.decode("Zg==Zg==").is_ok());
+	/// assert!(encoding.mode(Mode::Strict).decode("Zg==Zg==").is_err());
+	/// ```
+	#[inline]
+	pub const fn mode(self, mode: Mode) -> Self {
+		WithPad { mode, ..self }
 	}
 }
 
@@ -61,4 +98,20 @@ impl<T> WithPad<'_, T> where Self: Encoding {
 	pub fn display<'a>(&'a self, bytes: &'a [u8]) -> crate::Display<'a, Self> {
 		crate::Display::new(self, bytes)
 	}
+
+	/// Ignores ASCII whitespace while decoding.
+	#[inline]
+	pub const fn skip_whitespace(&self) -> crate::SkipWhitespace<'_, Self> {
+		crate::SkipWhitespace::new(self)
+	}
+
+	/// Wraps the encoded output in lines of `width` characters.
+	///
+	/// # Panics
+	///
+	/// Panics if the width is zero.
+	#[inline]
+	pub const fn wrap(&self, width: usize, ending: crate::LineEnding) -> crate::Wrap<'_, Self> {
+		crate::Wrap::new(self, width, ending)
+	}
 }
@@ -43,6 +43,29 @@ impl Ratio {
 		}
 	}
 
+	/// Estimates the maximum length of the encoded data with line wrapping given the length of the decoded data.
+	///
+	/// A separator of `ending_len` bytes is inserted every `width` encoded characters, not after the last line.
+	///
+	/// # Panics
+	///
+	/// Panics if the width is zero or if the result overflows `usize`.
+	#[inline]
+	pub const fn estimate_wrapped_len(&self, len: usize, width: usize, ending_len: usize) -> usize {
+		let encoded_len = self.estimate_encoded_len(len);
+		if encoded_len == 0 {
+			return 0;
+		}
+		let nseparators = (encoded_len - 1) / width;
+		match nseparators.checked_mul(ending_len) {
+			Some(len) => match encoded_len.checked_add(len) {
+				Some(len) => len,
+				None => panic_overflow(),
+			},
+			None => panic_overflow(),
+		}
+	}
+
 	/// Computes the chunk size for a given buffer length to incrementally encode the data.
 	///
 	/// The chunk size is always a multiple of the decoded length to ensure no padding is inserted.
@@ -63,7 +86,7 @@ impl Ratio {
 }
 
 #[cold]
-const fn panic_overflow() -> ! {
+pub(crate) const fn panic_overflow() -> ! {
 	panic!("overflow")
 }
 
@@ -88,6 +111,14 @@ fn test_ratio() {
 	assert_eq!(ratio.estimate_decoded_len(6), 6);
 	assert_eq!(ratio.estimate_decoded_len(7), 6);
 
+	assert_eq!(ratio.estimate_wrapped_len(0, 4, 2), 0);
+	assert_eq!(ratio.estimate_wrapped_len(3, 4, 2), 4);
+	assert_eq!(ratio.estimate_wrapped_len(4, 4, 2), 10);
+	assert_eq!(ratio.estimate_wrapped_len(6, 4, 2), 10);
+	assert_eq!(ratio.estimate_wrapped_len(6, 3, 1), 10);
+	assert_eq!(ratio.estimate_wrapped_len(57, 76, 2), 76);
+	assert_eq!(ratio.estimate_wrapped_len(58, 76, 2), 82);
+
 	assert_eq!(ratio.encoding_chunk_size(11), 6);
 	assert_eq!(ratio.encoding_chunk_size(12), 9);
 	assert_eq!(ratio.encoding_chunk_size(13), 9);
@@ -0,0 +1,138 @@
+/*!
+Self-contained SHA-256 used for checksums.
+*/
+
+const K: [u32; 64] = [
+	0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+	0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+	0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+	0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+	0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+	0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+	0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+	0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H: [u32; 8] = [
+	0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// SHA-256 hasher.
+#[derive(Clone)]
+pub(crate) struct Sha256 {
+	state: [u32; 8],
+	block: [u8; 64],
+	block_len: usize,
+	len: u64,
+}
+
+impl Sha256 {
+	/// Constructor.
+	#[inline]
+	pub(crate) const fn new() -> Sha256 {
+		Sha256 { state: H, block: [0; 64], block_len: 0, len: 0 }
+	}
+
+	/// Hashes the bytes.
+	pub(crate) fn update(&mut self, mut bytes: &[u8]) {
+		self.len = self.len.wrapping_add(bytes.len() as u64);
+
+		while !bytes.is_empty() {
+			let len = core::cmp::min(64 - self.block_len, bytes.len());
+			self.block[self.block_len..self.block_len + len].copy_from_slice(&bytes[..len]);
+			self.block_len += len;
+			bytes = &bytes[len..];
+
+			if self.block_len == 64 {
+				compress(&mut self.state, &self.block);
+				self.block_len = 0;
+			}
+		}
+	}
+
+	/// Returns the digest.
+	pub(crate) fn finish(mut self) -> [u8; 32] {
+		let bit_len = self.len.wrapping_mul(8);
+
+		// Append the bit 1, zeroes and the message length in bits
+		self.block[self.block_len] = 0x80;
+		self.block[self.block_len + 1..].fill(0);
+		if self.block_len >= 56 {
+			compress(&mut self.state, &self.block);
+			self.block.fill(0);
+		}
+		self.block[56..].copy_from_slice(&bit_len.to_be_bytes());
+		compress(&mut self.state, &self.block);
+
+		let mut digest = [0; 32];
+		for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+			chunk.copy_from_slice(&word.to_be_bytes());
+		}
+		digest
+	}
+}
+
+/// Double SHA-256 of the concatenated parts as used by Bitcoin.
+pub(crate) fn sha256d(parts: &[&[u8]]) -> [u8; 32] {
+	let mut hasher = Sha256::new();
+	for part in parts {
+		hasher.update(part);
+	}
+	let digest = hasher.finish();
+	let mut hasher = Sha256::new();
+	hasher.update(&digest);
+	hasher.finish()
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+	let mut w = [0u32; 64];
+	for (i, chunk) in block.chunks_exact(4).enumerate() {
+		w[i] = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+	}
+	for i in 16..64 {
+		let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+		let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+		w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+	}
+
+	let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+	for i in 0..64 {
+		let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+		let ch = (e & f) ^ (!e & g);
+		let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+		let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+		let maj = (a & b) ^ (a & c) ^ (b & c);
+		let t2 = s0.wrapping_add(maj);
+
+		h = g;
+		g = f;
+		f = e;
+		e = d.wrapping_add(t1);
+		d = c;
+		c = b;
+		b = a;
+		a = t1.wrapping_add(t2);
+	}
+
+	for (word, x) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+		*word = word.wrapping_add(x);
+	}
+}
+
+#[test]
+fn test_sha256() {
+	let digest = |bytes: &[u8]| {
+		let mut hasher = Sha256::new();
+		hasher.update(bytes);
+		hasher.finish()
+	};
+	assert_eq!(digest(b""), *b"\xe3\xb0\xc4\x42\x98\xfc\x1c\x14\x9a\xfb\xf4\xc8\x99\x6f\xb9\x24\x27\xae\x41\xe4\x64\x9b\x93\x4c\xa4\x95\x99\x1b\x78\x52\xb8\x55");
+	assert_eq!(digest(b"abc"), *b"\xba\x78\x16\xbf\x8f\x01\xcf\xea\x41\x41\x40\xde\x5d\xae\x22\x23\xb0\x03\x61\xa3\x96\x17\x7a\x9c\xb4\x10\xff\x61\xf2\x00\x15\xad");
+	assert_eq!(digest(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"), *b"\x24\x8d\x6a\x61\xd2\x06\x38\xb8\xe5\xc0\x26\x93\x0c\x3e\x60\x39\xa3\x3c\xe4\x59\x64\xff\x21\x67\xf6\xec\xed\xd4\x19\xdb\x06\xc1");
+
+	let mut hasher = Sha256::new();
+	for _ in 0..1000 {
+		hasher.update(&[b'a'; 1000]);
+	}
+	assert_eq!(hasher.finish(), *b"\xcd\xc7\x6e\x5c\x99\x14\xfb\x92\x81\xa1\xc7\xe2\x84\xd7\x3e\x67\xf1\x80\x9a\x48\xa4\x97\x20\x0e\x04\x6d\x39\xcc\xc7\x11\x2c\xd0");
+}
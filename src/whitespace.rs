@@ -0,0 +1,105 @@
+use super::*;
+
+/// Encoding ignoring ASCII whitespace while decoding.
+///
+/// Whitespace is any of the characters recognized by [`u8::is_ascii_whitespace`] and may appear anywhere in the input.
+/// This is useful to decode line wrapped MIME and PEM payloads without first stripping the whitespace.
+///
+/// Error offsets point into the original input, whitespace included.
+///
+/// # Examples
+///
+/// ```
+/// let decoded = basenc::Base64Std.skip_whitespace().decode("aGVs\r\nbG8g\r\nd29y\r\nbGQ=\r\n").unwrap();
+/// assert_eq!(decoded, b"hello world");
+/// ```
+#[derive(Clone, Debug)]
+pub struct SkipWhitespace<'a, T> {
+	encoding: &'a T,
+}
+
+impl<'a, T> SkipWhitespace<'a, T> {
+	/// Constructor.
+	#[inline]
+	pub const fn new(encoding: &'a T) -> Self {
+		SkipWhitespace { encoding }
+	}
+}
+
+impl<T: Encoding> SkipWhitespace<'_, T> {
+	#[cfg(feature = "std")]
+	/// Decodes the input string.
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		decode(self.encoding, string.as_bytes(), std::vec::Vec::new())
+	}
+
+	/// Decodes into a buffer.
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		decode(self.encoding, string.as_bytes(), buffer)
+	}
+}
+
+//----------------------------------------------------------------
+
+mod scalar;
+
+cfg_if::cfg_if! {
+	if #[cfg(all(not(feature = "simd-off"), any(target_arch = "x86_64", target_arch = "x86"), target_feature = "sse2"))] {
+		mod sse2;
+		use self::sse2::compact;
+	}
+	else {
+		use self::scalar::compact;
+	}
+}
+
+#[inline(never)]
+fn decode<T: Encoding, B: DecodeBuf>(encoding: &T, string: &[u8], mut buffer: B) -> Result<B::Output, Error> {
+	let dest_len = T::RATIO.estimate_decoded_len(string.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+		let dest = slice::from_raw_parts_mut(dest as *mut mem::MaybeUninit<u8>, dest_len);
+		let len = decode_compacted(encoding, string, dest).map_err(|err| locate(string, err))?;
+		Ok(buffer.commit(len))
+	}
+}
+
+// Compacts the string in chunks and feeds them to an incremental decoder
+fn decode_compacted<T: Encoding>(encoding: &T, mut string: &[u8], dest: &mut [mem::MaybeUninit<u8>]) -> Result<usize, Error> {
+	let mut stack_buf = Scrub([0u8; 512]);
+	let mut decoder = incremental::Decoder::new(encoding);
+	let mut len = 0;
+
+	while !string.is_empty() {
+		let (consumed, written) = compact(string, &mut *stack_buf);
+		len += decoder.update(&stack_buf[..written], &mut dest[len..])?.len();
+		string = &string[consumed..];
+	}
+
+	len += decoder.finish(&mut dest[len..])?.len();
+	Ok(len)
+}
+
+// Maps the error offset in the compacted input back to the original input
+#[cold]
+fn locate(string: &[u8], err: Error) -> Error {
+	let Some(offset) = err.offset()
+	else {
+		return err;
+	};
+
+	let mut remaining = offset;
+	for (i, &byte) in string.iter().enumerate() {
+		if !byte.is_ascii_whitespace() {
+			if remaining == 0 {
+				return err.offset_by(i - offset);
+			}
+			remaining -= 1;
+		}
+	}
+
+	err
+}
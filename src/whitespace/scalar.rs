@@ -0,0 +1,18 @@
+/// Copies the non-whitespace characters from string to dest.
+///
+/// Returns the number of characters consumed from string and written to dest.
+pub fn compact(string: &[u8], dest: &mut [u8]) -> (usize, usize) {
+	let mut consumed = 0;
+	let mut written = 0;
+
+	while consumed < string.len() && written < dest.len() {
+		let byte = string[consumed];
+		if !byte.is_ascii_whitespace() {
+			dest[written] = byte;
+			written += 1;
+		}
+		consumed += 1;
+	}
+
+	(consumed, written)
+}
@@ -0,0 +1,72 @@
+#[cfg(target_arch = "x86")]
+use core::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::*;
+
+use super::*;
+
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn whitespace_mask(block: __m128i) -> __m128i {
+	let space = _mm_cmpeq_epi8(block, _mm_set1_epi8(b' ' as i8));
+	let tab = _mm_cmpeq_epi8(block, _mm_set1_epi8(b'\t' as i8));
+	let lf = _mm_cmpeq_epi8(block, _mm_set1_epi8(b'\n' as i8));
+	let ff = _mm_cmpeq_epi8(block, _mm_set1_epi8(b'\x0C' as i8));
+	let cr = _mm_cmpeq_epi8(block, _mm_set1_epi8(b'\r' as i8));
+	_mm_or_si128(_mm_or_si128(space, tab), _mm_or_si128(_mm_or_si128(lf, ff), cr))
+}
+
+// Moves the bytes whose shift has bit K set K lanes to the left.
+// Moving by the lowest bit first the bytes never collide, the moved bytes take precedence over the whitespace left behind.
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn step<const K: i32>(data: __m128i, shift: __m128i) -> (__m128i, __m128i) {
+	let bit = _mm_set1_epi8(K as i8);
+	let moving = _mm_cmpeq_epi8(_mm_and_si128(shift, bit), bit);
+	let arriving = _mm_srli_si128::<K>(moving);
+	let data = _mm_or_si128(_mm_andnot_si128(arriving, data), _mm_srli_si128::<K>(_mm_and_si128(data, moving)));
+	let shift = _mm_or_si128(_mm_andnot_si128(_mm_or_si128(arriving, moving), shift), _mm_srli_si128::<K>(_mm_and_si128(shift, moving)));
+	(data, shift)
+}
+
+// Packs the non-whitespace bytes of the block to the left
+#[inline]
+#[target_feature(enable = "sse2")]
+unsafe fn left_pack(block: __m128i, whitespace: __m128i) -> __m128i {
+	// Every byte shifts left by the number of whitespace bytes before it
+	let mut count = _mm_sub_epi8(_mm_setzero_si128(), whitespace);
+	count = _mm_add_epi8(count, _mm_slli_si128::<1>(count));
+	count = _mm_add_epi8(count, _mm_slli_si128::<2>(count));
+	count = _mm_add_epi8(count, _mm_slli_si128::<4>(count));
+	count = _mm_add_epi8(count, _mm_slli_si128::<8>(count));
+	let shift = _mm_andnot_si128(whitespace, count);
+
+	let (data, shift) = step::<1>(block, shift);
+	let (data, shift) = step::<2>(data, shift);
+	let (data, shift) = step::<4>(data, shift);
+	let (data, _) = step::<8>(data, shift);
+	data
+}
+
+/// Copies the non-whitespace characters from string to dest.
+///
+/// Blocks of 16 characters are compacted in registers, whitespace-free blocks are copied as is.
+pub fn compact(string: &[u8], dest: &mut [u8]) -> (usize, usize) {
+	let mut consumed = 0;
+	let mut written = 0;
+
+	while string.len() - consumed >= 16 && dest.len() - written >= 16 {
+		unsafe {
+			let block = _mm_loadu_si128(string.as_ptr().add(consumed) as *const __m128i);
+			let whitespace = whitespace_mask(block);
+			let mask = _mm_movemask_epi8(whitespace);
+			let packed = if mask == 0 { block } else { left_pack(block, whitespace) };
+			_mm_storeu_si128(dest.as_mut_ptr().add(written) as *mut __m128i, packed);
+			consumed += 16;
+			written += 16 - mask.count_ones() as usize;
+		}
+	}
+
+	let (n, m) = scalar::compact(&string[consumed..], &mut dest[written..]);
+	(consumed + n, written + m)
+}
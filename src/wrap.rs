@@ -0,0 +1,158 @@
+use super::*;
+
+/// Line ending.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum LineEnding {
+	/// Line feed `\n`.
+	#[default]
+	Lf,
+	/// Carriage return and line feed `\r\n`.
+	CrLf,
+}
+
+impl LineEnding {
+	/// Returns the line separator.
+	#[inline]
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			LineEnding::Lf => "\n",
+			LineEnding::CrLf => "\r\n",
+		}
+	}
+}
+
+/// Encoding with line wrapping.
+///
+/// Inserts a line separator every `width` characters of the encoded output.
+/// No separator is added after the last line.
+///
+/// Decoding ignores all ASCII whitespace, see [`SkipWhitespace`].
+///
+/// # Examples
+///
+/// ```
+/// use basenc::{LineEnding, Padding};
+///
+/// let encoding = basenc::Base64Std.pad(Padding::Strict);
+/// let encoded = encoding.wrap(8, LineEnding::CrLf).encode(b"hello world");
+/// assert_eq!(encoded, "aGVsbG8g\r\nd29ybGQ=");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Wrap<'a, T> {
+	encoding: &'a T,
+	width: usize,
+	ending: LineEnding,
+}
+
+impl<'a, T> Wrap<'a, T> {
+	/// Constructor.
+	///
+	/// # Panics
+	///
+	/// Panics if the width is zero.
+	#[inline]
+	pub const fn new(encoding: &'a T, width: usize, ending: LineEnding) -> Self {
+		assert!(width > 0, "line width must be non-zero");
+		Wrap { encoding, width, ending }
+	}
+}
+
+impl<T: Encoding> Wrap<'_, T> {
+	#[cfg(feature = "std")]
+	/// Encodes the input bytes.
+	#[inline]
+	pub fn encode(&self, bytes: &[u8]) -> std::string::String {
+		encode(self, bytes, std::string::String::new())
+	}
+
+	#[cfg(feature = "std")]
+	/// Decodes the input string.
+	#[inline]
+	pub fn decode(&self, string: &str) -> Result<std::vec::Vec<u8>, Error> {
+		SkipWhitespace::new(self.encoding).decode(string)
+	}
+
+	/// Encodes into a buffer.
+	///
+	/// Use [`Ratio::estimate_wrapped_len`] to size the buffer.
+	#[inline]
+	pub fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		encode(self, bytes, buffer)
+	}
+
+	/// Decodes into a buffer.
+	#[inline]
+	pub fn decode_into<B: DecodeBuf>(&self, string: &str, buffer: B) -> Result<B::Output, Error> {
+		SkipWhitespace::new(self.encoding).decode_into(string, buffer)
+	}
+
+	/// Wraps the encoding and bytes for display.
+	#[inline]
+	pub fn display<'a>(&'a self, bytes: &'a [u8]) -> Display<'a, Self> {
+		Display { encoding: self, bytes }
+	}
+}
+
+impl<'a, T: Encoding> fmt::Display for Display<'a, Wrap<'_, T>> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let Wrap { encoding, width, ending } = *self.encoding;
+
+		let mut stack_buf = Scrub(mem::MaybeUninit::<[u8; 512]>::uninit());
+		let chunk_size = T::RATIO.encoding_chunk_size(mem::size_of_val(&stack_buf));
+
+		// Number of characters in the current line
+		let mut column = 0;
+
+		for chunk in self.bytes.chunks(chunk_size) {
+			let mut string = encoding.encode_into(chunk, &mut *stack_buf);
+
+			while !string.is_empty() {
+				if column == width {
+					f.write_str(ending.as_str())?;
+					column = 0;
+				}
+
+				let len = cmp::min(width - column, string.len());
+				f.write_str(&string[..len])?;
+				string = &string[len..];
+				column += len;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[inline(never)]
+fn encode<T: Encoding, B: EncodeBuf>(wrap: &Wrap<'_, T>, bytes: &[u8], mut buffer: B) -> B::Output {
+	let ending = wrap.ending.as_str().as_bytes();
+	let encoded_len = T::RATIO.estimate_encoded_len(bytes.len());
+	let dest_len = T::RATIO.estimate_wrapped_len(bytes.len(), wrap.width, ending.len());
+
+	unsafe {
+		let dest = buffer.allocate(dest_len);
+
+		// Encode into the end of the buffer
+		let start = dest_len - encoded_len;
+		let encoded = slice::from_raw_parts_mut(dest.add(start) as *mut mem::MaybeUninit<u8>, encoded_len);
+		let len = wrap.encoding.encode_into(bytes, encoded).len();
+
+		// Move the lines into place, the destination never overtakes the source
+		let mut src = start;
+		let mut end = 0;
+		let mut remaining = len;
+		while remaining > 0 {
+			if end > 0 {
+				ptr::copy_nonoverlapping(ending.as_ptr(), dest.add(end), ending.len());
+				end += ending.len();
+			}
+			let line_len = cmp::min(wrap.width, remaining);
+			ptr::copy(dest.add(src), dest.add(end), line_len);
+			src += line_len;
+			end += line_len;
+			remaining -= line_len;
+		}
+
+		buffer.commit(end)
+	}
+}
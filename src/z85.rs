@@ -0,0 +1,118 @@
+use super::*;
+
+const RATIO: Ratio = Ratio { decoded: 4, encoded: 5 };
+
+static ALPHABET: Base85 = Base85::new(b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#");
+
+/// Z85 encoding as specified by ZeroMQ RFC 32.
+///
+/// The input length must be a multiple of 4 and the encoded length a multiple of 5.
+/// Decoding a string of any other length returns [`Error::IncorrectLength`].
+/// See [`Z85Padded`] for inputs of any length.
+///
+/// # Panics
+///
+/// Encoding panics if the input length is not a multiple of 4.
+/// This includes displaying the bytes and finishing an [`incremental::Encoder`] or [`incremental::EncoderWriter`] after an incomplete group.
+#[derive(Clone, Debug)]
+pub struct Z85;
+
+impl Encoding for Z85 {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		assert!(bytes.len().is_multiple_of(4), "Z85 input length must be a multiple of 4, use Z85Padded for inputs of any length");
+		base85::encode(bytes, &ALPHABET, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		if !string.len().is_multiple_of(5) {
+			return Err(Error::IncorrectLength);
+		}
+		base85::decode(string, &ALPHABET, buffer)
+	}
+}
+
+impl_encoding!(Z85,
+	encode: [
+		"```",
+		"let encoded = basenc::Z85.encode(b\"\\x86\\x4F\\xD2\\x6F\\xB5\\x59\\xF7\\x5B\");",
+		"assert_eq!(encoded, \"HelloWorld\");",
+		"```",
+	],
+	decode: [
+		"```",
+		"let decoded = basenc::Z85.decode(\"HelloWorld\").unwrap();",
+		"assert_eq!(decoded, b\"\\x86\\x4F\\xD2\\x6F\\xB5\\x59\\xF7\\x5B\");",
+		"",
+		"assert_eq!(basenc::Z85.decode(\"Hello\\x20World\"), Err(basenc::Error::IncorrectLength));",
+		"```",
+	],
+	encode_into: [
+		"```",
+		"let mut stack_buf = [0u8; 16];",
+		"let encoded = basenc::Z85.encode_into(b\"\\x86\\x4F\\xD2\\x6F\", &mut stack_buf);",
+		"assert_eq!(encoded, \"Hello\");",
+		"```",
+	],
+	decode_into: [
+		"```",
+		"let decoded = basenc::Z85.decode_into(\"World\", Vec::new()).unwrap();",
+		"assert_eq!(decoded, b\"\\xB5\\x59\\xF7\\x5B\");",
+		"```",
+	],
+);
+impl_whitespace!(Z85);
+
+//----------------------------------------------------------------
+
+/// Z85 encoding for inputs of any length.
+///
+/// A final partial group of `n` bytes is encoded as `n + 1` characters, identical to [`Z85`] for inputs with a length multiple of 4.
+#[derive(Clone, Debug)]
+pub struct Z85Padded;
+
+impl Encoding for Z85Padded {
+	const RATIO: Ratio = RATIO;
+
+	#[inline]
+	fn encode_into<B: EncodeBuf>(&self, bytes: &[u8], buffer: B) -> B::Output {
+		base85::encode(bytes, &ALPHABET, buffer)
+	}
+
+	#[inline]
+	fn decode_into<B: DecodeBuf>(&self, string: &[u8], buffer: B) -> Result<B::Output, Error> {
+		base85::decode(string, &ALPHABET, buffer)
+	}
+}
+
+impl_encoding!(Z85Padded,
+	encode: [
+		"```",
+		"let encoded = basenc::Z85Padded.encode(b\"\\x86\\x4F\\xD2\\x6F\\xB5\");",
+		"assert_eq!(encoded, \"HelloWe\");",
+		"```",
+	],
+	decode: [
+		"```",
+		"let decoded = basenc::Z85Padded.decode(\"HelloWe\").unwrap();",
+		"assert_eq!(decoded, b\"\\x86\\x4F\\xD2\\x6F\\xB5\");",
+		"```",
+	],
+	encode_into: [
+		"```",
+		"let mut stack_buf = [0u8; 16];",
+		"let encoded = basenc::Z85Padded.encode_into(b\"\\x86\\x4F\\xD2\", &mut stack_buf);",
+		"assert_eq!(encoded, \"Helj\");",
+		"```",
+	],
+	decode_into: [
+		"```",
+		"let decoded = basenc::Z85Padded.decode_into(\"Helj\", Vec::new()).unwrap();",
+		"assert_eq!(decoded, b\"\\x86\\x4F\\xD2\");",
+		"```",
+	],
+);
+impl_whitespace!(Z85Padded);
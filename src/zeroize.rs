@@ -0,0 +1,238 @@
+/*!
+Wiping sensitive memory.
+*/
+
+use core::{fmt, ops};
+#[cfg(feature = "zeroize")]
+use core::{mem, ptr, sync::atomic};
+
+/// Overwrites the memory with zeroes.
+///
+/// The writes are volatile so they are not optimized away even though the memory is never read again.
+#[cfg(feature = "zeroize")]
+#[inline(never)]
+pub(crate) unsafe fn wipe(ptr: *mut u8, len: usize) {
+	for i in 0..len {
+		ptr::write_volatile(ptr.add(i), 0);
+	}
+	atomic::compiler_fence(atomic::Ordering::SeqCst);
+}
+
+//----------------------------------------------------------------
+
+/// Internal temporary which is wiped when dropped.
+///
+/// Does nothing unless the `zeroize` feature is enabled.
+#[derive(Clone)]
+pub(crate) struct Scrub<T>(pub(crate) T);
+
+impl<T> ops::Deref for Scrub<T> {
+	type Target = T;
+	#[inline]
+	fn deref(&self) -> &T {
+		&self.0
+	}
+}
+impl<T> ops::DerefMut for Scrub<T> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut T {
+		&mut self.0
+	}
+}
+
+impl<T> fmt::Debug for Scrub<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("..")
+	}
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> Drop for Scrub<T> {
+	#[inline]
+	fn drop(&mut self) {
+		unsafe { wipe(&mut self.0 as *mut T as *mut u8, mem::size_of::<T>()) }
+	}
+}
+
+//----------------------------------------------------------------
+
+/// Owned byte buffer which wipes its memory.
+///
+/// The contents are overwritten with zeroes when dropped and when the buffer grows, before the old memory is released.
+///
+/// Implements [`DecodeBuf`](crate::DecodeBuf) and [`EncodeBuf`](crate::EncodeBuf) appending to the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use basenc::Zeroizing;
+///
+/// let key = basenc::Base64Std.decode_into("c2VjcmV0IGtleQ==", Zeroizing::new()).unwrap();
+/// assert_eq!(&key[..], b"secret key");
+///
+/// let mut encoded = Zeroizing::new();
+/// assert_eq!(basenc::LowerHex.encode_into(&key, &mut encoded), "736563726574206b6579");
+/// ```
+#[cfg(all(feature = "zeroize", feature = "std"))]
+#[derive(Default)]
+pub struct Zeroizing {
+	vec: std::vec::Vec<u8>,
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl Zeroizing {
+	/// Constructs a new, empty buffer.
+	#[inline]
+	pub const fn new() -> Zeroizing {
+		Zeroizing { vec: std::vec::Vec::new() }
+	}
+
+	/// Constructs a new, empty buffer with at least the specified capacity.
+	#[inline]
+	pub fn with_capacity(capacity: usize) -> Zeroizing {
+		Zeroizing { vec: std::vec::Vec::with_capacity(capacity) }
+	}
+
+	/// Returns the bytes as a string slice if they are valid UTF-8.
+	#[inline]
+	pub fn to_str(&self) -> Result<&str, core::str::Utf8Error> {
+		core::str::from_utf8(&self.vec)
+	}
+
+	/// Wipes and clears the buffer, keeping the allocated capacity.
+	#[inline]
+	pub fn clear(&mut self) {
+		unsafe { wipe(self.vec.as_mut_ptr(), self.vec.len()) }
+		self.vec.clear();
+	}
+
+	/// Reserves capacity for at least `additional` more bytes.
+	///
+	/// Unlike [`Vec::reserve`](std::vec::Vec::reserve) the old memory is wiped before it is released.
+	pub fn reserve(&mut self, additional: usize) {
+		if self.vec.capacity() - self.vec.len() >= additional {
+			return;
+		}
+		let capacity = self.vec.len().checked_add(additional).expect("capacity overflow");
+		let capacity = core::cmp::max(capacity, self.vec.capacity() * 2);
+		let mut vec = std::vec::Vec::with_capacity(capacity);
+		vec.extend_from_slice(&self.vec);
+		mem::swap(&mut self.vec, &mut vec);
+		unsafe { wipe(vec.as_mut_ptr(), vec.capacity()) }
+	}
+
+	#[inline]
+	unsafe fn allocate(&mut self, len: usize) -> *mut u8 {
+		self.reserve(len);
+		self.vec.as_mut_ptr().add(self.vec.len())
+	}
+
+	#[inline]
+	unsafe fn commit(&mut self, len: usize) -> &[u8] {
+		let start = self.vec.len();
+		self.vec.set_len(start + len);
+		&self.vec[start..]
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl From<std::vec::Vec<u8>> for Zeroizing {
+	#[inline]
+	fn from(vec: std::vec::Vec<u8>) -> Zeroizing {
+		Zeroizing { vec }
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl ops::Deref for Zeroizing {
+	type Target = [u8];
+	#[inline]
+	fn deref(&self) -> &[u8] {
+		&self.vec
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl AsRef<[u8]> for Zeroizing {
+	#[inline]
+	fn as_ref(&self) -> &[u8] {
+		&self.vec
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl fmt::Debug for Zeroizing {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Zeroizing").field("len", &self.vec.len()).finish_non_exhaustive()
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl Drop for Zeroizing {
+	#[inline]
+	fn drop(&mut self) {
+		unsafe { wipe(self.vec.as_mut_ptr(), self.vec.capacity()) }
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl crate::DecodeBuf for Zeroizing {
+	type Output = Zeroizing;
+	#[inline]
+	unsafe fn allocate(&mut self, len: usize) -> *mut u8 {
+		Zeroizing::allocate(self, len)
+	}
+	#[inline]
+	unsafe fn commit(mut self, len: usize) -> Self::Output {
+		Zeroizing::commit(&mut self, len);
+		self
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl<'a> crate::DecodeBuf for &'a mut Zeroizing {
+	type Output = &'a [u8];
+	#[inline]
+	unsafe fn allocate(&mut self, len: usize) -> *mut u8 {
+		Zeroizing::allocate(self, len)
+	}
+	#[inline]
+	unsafe fn commit(self, len: usize) -> Self::Output {
+		Zeroizing::commit(self, len)
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl crate::EncodeBuf for Zeroizing {
+	type Output = Zeroizing;
+	#[inline]
+	unsafe fn allocate(&mut self, len: usize) -> *mut u8 {
+		Zeroizing::allocate(self, len)
+	}
+	#[inline]
+	unsafe fn commit(mut self, len: usize) -> Self::Output {
+		Zeroizing::commit(&mut self, len);
+		self
+	}
+}
+
+#[cfg(all(feature = "zeroize", feature = "std"))]
+impl<'a> crate::EncodeBuf for &'a mut Zeroizing {
+	type Output = &'a str;
+	#[inline]
+	unsafe fn allocate(&mut self, len: usize) -> *mut u8 {
+		Zeroizing::allocate(self, len)
+	}
+	#[inline]
+	unsafe fn commit(self, len: usize) -> Self::Output {
+		core::str::from_utf8_unchecked(Zeroizing::commit(self, len))
+	}
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_scrub() {
+	let mut temp = mem::ManuallyDrop::new(Scrub([0xa5u8; 64]));
+	unsafe { mem::ManuallyDrop::drop(&mut temp) };
+	assert_eq!(temp.0, [0u8; 64]);
+}
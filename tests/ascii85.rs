@@ -0,0 +1,93 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(input: &[u8], encoding: &Ascii85, expected: &str) {
+	assert_eq!(expected, encoding.encode(input));
+	assert_eq!(Ok(input), encoding.decode(expected).as_deref());
+}
+
+#[test]
+fn adobe() {
+	roundtrip(b"", &Ascii85Adobe, "");
+	roundtrip(b"h", &Ascii85Adobe, "BE");
+	roundtrip(b"he", &Ascii85Adobe, "BOq");
+	roundtrip(b"hel", &Ascii85Adobe, "BOtu");
+	roundtrip(b"hell", &Ascii85Adobe, "BOu!r");
+	roundtrip(b"hello", &Ascii85Adobe, "BOu!rDZ");
+	roundtrip(b"\0\0\0\0", &Ascii85Adobe, "z");
+	roundtrip(b"\0\0\0", &Ascii85Adobe, "!!!!");
+	roundtrip(b"    ", &Ascii85Adobe, "+<VdL");
+	roundtrip(b"\xff\xff\xff\xff", &Ascii85Adobe, "s8W-!");
+	roundtrip(b"Man is distinguished", &Ascii85Adobe, "9jqo^BlbD-BleB1DJ+*+F(f,q");
+}
+
+#[test]
+fn btoa() {
+	roundtrip(b"    ", &Ascii85Btoa, "y");
+	roundtrip(b"\0\0\0\0    \0\0\0\0", &Ascii85Btoa, "zyz");
+	roundtrip(b"   ", &Ascii85Btoa, "+<Vd");
+	assert_eq!(Ascii85Adobe.decode("y"), Err(Error::InvalidCharacter { offset: 0, byte: b'y' }));
+}
+
+#[test]
+fn shortcuts_buffer() {
+	// Every shortcut decodes to four bytes, more than the ratio estimate
+	let string = "zzzzzzzzzz";
+	assert!(Ascii85::RATIO.estimate_decoded_len(string.len()) < 40);
+	let mut stack_buf = [0u8; 64];
+	let len = Ascii85Adobe.estimate_decoded_len(string);
+	assert!(len >= 40 && len <= stack_buf.len());
+	assert_eq!(Ascii85Adobe.decode_into(string, &mut stack_buf[..len]).unwrap(), &[0u8; 40]);
+	assert_eq!(Encoding::decode_into(&Ascii85Btoa, b"zyz", Vec::new()).unwrap(), b"\0\0\0\0    \0\0\0\0");
+}
+
+#[test]
+fn delimited() {
+	let delimited = Ascii85Adobe.delimited();
+	assert_eq!(delimited.encode(b""), "<~~>");
+	assert_eq!(delimited.encode(b"hell\0\0\0\0"), "<~BOu!rz~>");
+	assert_eq!(delimited.display(b"hell\0\0\0\0").to_string(), "<~BOu!rz~>");
+	assert_eq!(delimited.decode("<~BOu!rz~>").unwrap(), b"hell\0\0\0\0");
+	assert_eq!(delimited.decode("\r\n  BOu!r\r\nz~>\n").unwrap(), b"hell\0\0\0\0");
+	assert_eq!(delimited.decode("<~BOu!rz"), Err(Error::IncorrectLength));
+	assert_eq!(delimited.decode(" <~BOu!{z~>"), Err(Error::InvalidCharacter { offset: 7, byte: b'{' }));
+
+	let mut stack_buf = [0u8; Ascii85Delimited::estimate_encoded_len(5)];
+	assert_eq!(delimited.encode_into(b"hello", &mut stack_buf), "<~BOu!rDZ~>");
+}
+
+#[test]
+fn errors() {
+	assert_eq!(Ascii85Adobe.decode("BOu!rD"), Err(Error::IncorrectLength));
+	assert_eq!(Ascii85Adobe.decode("BOz"), Err(Error::InvalidCharacter { offset: 2, byte: b'z' }));
+	assert_eq!(Ascii85Adobe.decode("BOu!v"), Err(Error::InvalidCharacter { offset: 4, byte: b'v' }));
+	assert_eq!(Ascii85Adobe.decode("s8W-\""), Err(Error::InvalidCharacter { offset: 4, byte: b'"' }));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 300];
+
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		// Sprinkle in some compressible groups
+		for group in input_buf[..len].chunks_exact_mut(4) {
+			match rng.range(0..8) {
+				0 => group.fill(0),
+				1 => group.fill(b' '),
+				_ => (),
+			}
+		}
+		let input = &input_buf[..len];
+
+		for encoding in [&Ascii85Adobe, &Ascii85Btoa] {
+			let encoded = encoding.encode(input);
+			assert!(encoded.len() <= Ascii85::RATIO.estimate_encoded_len(len));
+			assert!(input.len() <= encoding.estimate_decoded_len(&encoded));
+			assert_eq!(encoding.decode(&encoded).unwrap(), input);
+			assert_eq!(encoding.display(input).to_string(), encoded);
+		}
+	}
+}
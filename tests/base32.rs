@@ -47,3 +47,41 @@ fn random() {
 	smash(&Base32Hex.pad(NoPad), &mut stack_buf);
 	smash(&Base32Z.pad(NoPad), &mut stack_buf);
 }
+
+#[test]
+fn error_offsets() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 120];
+	rng.fill_bytes(&mut input);
+	let encoded = Base32Std.encode(&input);
+
+	for offset in 0..encoded.len() {
+		let mut string = encoded.clone().into_bytes();
+		string[offset] = b'*';
+		assert_eq!(Encoding::decode_into(&Base32Std, &string, Vec::new()), Err(Error::InvalidCharacter { offset, byte: b'*' }));
+	}
+
+	let string = "MZXW6YTBOI======MZ======";
+	assert_eq!(Base32Std.decode(string), Err(Error::NonCanonical { offset: 16 }));
+}
+
+#[test]
+fn strict_mode() {
+	let normal = Base32Std.pad(Padding::Strict);
+	let strict = Base32Std.pad(Padding::Strict).mode(Mode::Strict);
+
+	assert_eq!(normal.decode("MY======MY======").unwrap(), b"ff");
+	assert_eq!(strict.decode("MY======MY======"), Err(Error::InvalidCharacter { offset: 2, byte: b'=' }));
+	assert_eq!(strict.decode("MZXW6YTBMZXW6YQ=MZXQ===="), Err(Error::InvalidCharacter { offset: 15, byte: b'=' }));
+	assert_eq!(strict.decode("MZXW6YTBMZXW6YQ=").unwrap(), b"foobafoob");
+}
+
+#[test]
+fn lenient_mode() {
+	let lenient = Base32Std.pad(Padding::Optional).mode(Mode::Lenient);
+	assert_eq!(lenient.decode("MZ======").unwrap(), b"f");
+	assert_eq!(lenient.decode("MZXR").unwrap(), b"fo");
+	assert_eq!(lenient.decode("MZXW7").unwrap(), b"foo");
+	assert_eq!(lenient.decode("MZXW6YR=").unwrap(), b"foob");
+	assert_eq!(Base32Std.decode("MZXW6YR="), Err(Error::NonCanonical { offset: 0 }));
+}
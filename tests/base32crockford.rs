@@ -0,0 +1,85 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(input: &[u8], expected: &str, check: &str) {
+	assert_eq!(expected, Base32Crockford.encode(input));
+	assert_eq!(Ok(input), Base32Crockford.decode(expected).as_deref());
+	assert_eq!(check, Base32Crockford.check().encode(input));
+	assert_eq!(Ok(input), Base32Crockford.check().decode(check).as_deref());
+}
+
+#[test]
+fn vectors() {
+	roundtrip(b"", "", "0");
+	roundtrip(b"\x00", "00", "000");
+	roundtrip(b"\x01\x02", "0410", "0410U");
+	roundtrip(b"hello", "D1JPRV3F", "D1JPRV3FJ");
+	roundtrip(b"foobar", "CSQPYRK1E8", "CSQPYRK1E86");
+	roundtrip(b"hello world", "D1JPRV3F41VPYWKCCG", "D1JPRV3F41VPYWKCCGS");
+}
+
+#[test]
+fn aliases() {
+	assert_eq!(Base32Crockford.decode("csqpyrk1e8").unwrap(), b"foobar");
+	assert_eq!(Base32Crockford.decode("CSQPYRKIE8").unwrap(), b"foobar");
+	assert_eq!(Base32Crockford.decode("CSQPYRKLE8").unwrap(), b"foobar");
+	assert_eq!(Base32Crockford.decode("oo").unwrap(), b"\x00");
+	assert_eq!(Base32Crockford.decode("CSQ-PYR-K1E-8").unwrap(), b"foobar");
+	assert_eq!(Base32Crockford.decode("-CSQPYRK1E8--").unwrap(), b"foobar");
+	assert_eq!(Base32Crockford.check().decode("0410u").unwrap(), b"\x01\x02");
+	assert_eq!(Base32Crockford.check().decode("04-10-U-").unwrap(), b"\x01\x02");
+}
+
+#[test]
+fn errors() {
+	assert_eq!(Base32Crockford.decode("CSQPYRUE"), Err(Error::InvalidCharacter { offset: 6, byte: b'U' }));
+	assert_eq!(Base32Crockford.decode("CSQ-PYR-UE"), Err(Error::InvalidCharacter { offset: 8, byte: b'U' }));
+	assert_eq!(Base32Crockford.decode("CSQPYRK1E9"), Err(Error::NonCanonical { offset: 8 }));
+	assert_eq!(Base32Crockford.decode("CSQP-YRK1-E9"), Err(Error::NonCanonical { offset: 10 }));
+	assert_eq!(Base32Crockford.decode("CSQPYRK1E"), Err(Error::IncorrectLength));
+
+	assert_eq!(Base32Crockford.check().decode(""), Err(Error::IncorrectLength));
+	assert_eq!(Base32Crockford.check().decode("--"), Err(Error::IncorrectLength));
+	assert_eq!(Base32Crockford.check().decode("0410*"), Err(Error::ChecksumMismatch));
+	assert_eq!(Base32Crockford.check().decode("0410#"), Err(Error::InvalidCharacter { offset: 4, byte: b'#' }));
+	assert_eq!(Base32Crockford.check().decode("04U0U"), Err(Error::InvalidCharacter { offset: 2, byte: b'U' }));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 256];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+
+		let input = &input_buf[..len];
+		let encoded = Base32Crockford.encode(input);
+		assert_eq!(Base32Crockford.decode(&encoded).unwrap(), input);
+		assert_eq!(Base32Crockford.decode(&encoded.to_ascii_lowercase()).unwrap(), input);
+
+		let hyphenated: String = encoded.chars().flat_map(|chr| [chr, '-']).collect();
+		assert_eq!(Base32Crockford.decode(&hyphenated).unwrap(), input);
+
+		let encoded = Base32Crockford.check().encode(input);
+		assert!(encoded.len() <= Base32CrockfordCheck::estimate_encoded_len(len));
+		assert_eq!(Base32Crockford.check().decode(&encoded).unwrap(), input);
+	}
+}
+
+#[test]
+fn adapters_reject_hyphens() {
+	let hyphenated = "D1JP-RV3F-41VP-YWKC-CG";
+	let err = Error::InvalidCharacter { offset: 4, byte: b'-' };
+	assert_eq!(Base32Crockford.skip_whitespace().decode(hyphenated), Err(err));
+
+	let mut reader = incremental::DecoderReader::new(&Base32Crockford, hyphenated.as_bytes());
+	let io_err = std::io::Read::read_to_end(&mut reader, &mut Vec::new()).unwrap_err();
+	assert_eq!(io_err.into_inner().unwrap().downcast_ref::<Error>(), Some(&err));
+
+	assert_eq!(Base32Crockford.skip_whitespace().decode("D1JPRV3F\r\n41VPYWKCCG").unwrap(), b"hello world");
+	let mut reader = incremental::DecoderReader::new(&Base32Crockford, &b"D1JPRV3F41VPYWKCCG"[..]);
+	let mut decoded = Vec::new();
+	std::io::Read::read_to_end(&mut reader, &mut decoded).unwrap();
+	assert_eq!(decoded, b"hello world");
+}
@@ -0,0 +1,44 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(input: &[u8], expected: &str) {
+	assert_eq!(expected, Base45Std.encode_into(input, String::new()));
+	assert_eq!(Ok(input), Base45Std.decode_into(expected, Vec::new()).as_deref());
+}
+
+#[test]
+fn rfc9285() {
+	roundtrip(b"", "");
+	roundtrip(b"AB", "BB8");
+	roundtrip(b"Hello!!", "%69 VD92EX0");
+	roundtrip(b"base-45", "UJCLQE7W581");
+	roundtrip(b"ietf!", "QED8WEX0");
+	roundtrip(b"\xff\xff", "FGW");
+	roundtrip(b"\xff", "U5");
+}
+
+#[test]
+fn errors() {
+	assert_eq!(Base45Std.decode("GGW"), Err(Error::OutOfRange { offset: 0 }));
+	assert_eq!(Base45Std.decode("BB8:::"), Err(Error::OutOfRange { offset: 3 }));
+	assert_eq!(Base45Std.decode("BB8V5"), Err(Error::OutOfRange { offset: 3 }));
+	assert_eq!(Base45Std.decode("BB8B"), Err(Error::IncorrectLength));
+	assert_eq!(Base45Std.decode("BB8aB"), Err(Error::InvalidCharacter { offset: 3, byte: b'a' }));
+	assert_eq!(Base45Std.decode("BB8BB="), Err(Error::InvalidCharacter { offset: 5, byte: b'=' }));
+	assert_eq!(Error::OutOfRange { offset: 3 }.offset(), Some(3));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 1024];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+
+		let input = &input_buf[..len];
+		let encoded = Base45Std.encode(input);
+		assert_eq!(Base45Std.display(input).to_string(), encoded);
+		assert_eq!(Base45Std.decode(&encoded).unwrap(), input);
+	}
+}
@@ -0,0 +1,114 @@
+use basenc::*;
+
+// Straightforward reference implementation, one digit at a time
+fn reference_encode(bytes: &[u8], charset: &[u8; 58]) -> String {
+	let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+	let mut digits: Vec<u8> = Vec::new();
+	for &byte in &bytes[zeros..] {
+		let mut carry = byte as u32;
+		for digit in digits.iter_mut() {
+			carry += (*digit as u32) << 8;
+			*digit = (carry % 58) as u8;
+			carry /= 58;
+		}
+		while carry != 0 {
+			digits.push((carry % 58) as u8);
+			carry /= 58;
+		}
+	}
+	let mut string = String::new();
+	string.extend((0..zeros).map(|_| charset[0] as char));
+	string.extend(digits.iter().rev().map(|&digit| charset[digit as usize] as char));
+	string
+}
+
+#[track_caller]
+fn roundtrip(hex: &str, expected: &str) {
+	let input = LowerHex.decode(hex).unwrap();
+	assert_eq!(expected, Base58Btc.encode(&input));
+	assert_eq!(Ok(input), Base58Btc.decode(expected));
+}
+
+#[test]
+fn bitcoin() {
+	roundtrip("", "");
+	roundtrip("61", "2g");
+	roundtrip("626262", "a3gV");
+	roundtrip("636363", "aPEr");
+	roundtrip("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2");
+	roundtrip("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L");
+	roundtrip("516b6fcd0f", "ABnLTmg");
+	roundtrip("bf4f89001e670274dd", "3SEo3LWLoPntC");
+	roundtrip("572e4794", "3EFU7m");
+	roundtrip("ecac89cad93923c02321", "EJDM8drfXA6uyA");
+	roundtrip("10c8511e", "Rt5zm");
+	roundtrip("00000000000000000000", "1111111111");
+	roundtrip("000111d38e5fc9071ffcd20b4a763cc9ae4f252bb4e48fd66a835e252ada93ff480d6dd43dc62a641155a5", "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+}
+
+#[test]
+fn alphabets() {
+	let input = b"\x00\x00hello world";
+	assert_eq!(Base58Ripple.encode(input), "rrStVrDLaUATiyKyV");
+	assert_eq!(Base58Flickr.encode(input), "11rTu1dk6cWsRYjYu");
+	assert_eq!(Base58Ripple.decode("rrStVrDLaUATiyKyV").unwrap(), input);
+	assert_eq!(Base58Flickr.decode("11rTu1dk6cWsRYjYu").unwrap(), input);
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 300];
+	let charsets = [
+		b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+		b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz",
+	];
+
+	for _ in 0..500 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		let zeros = rng.range(0..4).min(len);
+		input_buf[..zeros].fill(0);
+		let input = &input_buf[..len];
+
+		for (base, charset) in [&Base58Btc, &Base58Ripple].into_iter().zip(charsets) {
+			let encoded = base.encode(input);
+			assert_eq!(encoded, reference_encode(input, charset));
+			assert!(encoded.len() <= Base58::estimate_encoded_len(len));
+			assert_eq!(base.decode(&encoded).unwrap(), input);
+		}
+	}
+}
+
+#[test]
+fn stack_buffers() {
+	for len in 0..100 {
+		let input = vec![0xff; len];
+		let mut stack_buf = [0u8; Base58::estimate_encoded_len(100)];
+		let encoded = Base58Btc.encode_into(&input, &mut stack_buf[..Base58::estimate_encoded_len(len)]);
+
+		let mut stack_buf = [0u8; Base58::estimate_decoded_len(200)];
+		let decoded = Base58Btc.decode_into(encoded, &mut stack_buf[..Base58::estimate_decoded_len(encoded.len())]).unwrap();
+		assert_eq!(decoded, &input[..]);
+	}
+
+	let string = "1".repeat(50);
+	assert_eq!(Base58Btc.decode(&string).unwrap(), [0u8; 50]);
+}
+
+#[test]
+fn large() {
+	let mut rng = urandom::new();
+	let mut input = vec![0u8; 4096];
+	rng.fill_bytes(&mut input);
+
+	let encoded = Base58Btc.encode(&input);
+	assert_eq!(Base58Btc.decode(&encoded).unwrap(), input);
+}
+
+#[test]
+fn errors() {
+	assert_eq!(Base58Btc.decode("11O1"), Err(Error::InvalidCharacter { offset: 2, byte: b'O' }));
+	assert_eq!(Base58Btc.decode("abcdefghijk0"), Err(Error::InvalidCharacter { offset: 11, byte: b'0' }));
+	assert_eq!(Base58Btc.decode("2g\u{e9}"), Err(Error::InvalidCharacter { offset: 2, byte: 0xc3 }));
+}
@@ -0,0 +1,36 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(version: u8, hex: &str, expected: &str) {
+	let payload = LowerHex.decode(hex).unwrap();
+	assert_eq!(expected, Base58Btc.check().encode(version, &payload));
+	assert_eq!(Ok((version, payload)), Base58Btc.check().decode(expected));
+}
+
+#[test]
+fn bitcoin() {
+	roundtrip(0x00, "62e907b15cbf27d5425399ebf6f0fb50ebb88f18", "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+	roundtrip(0x00, "0000000000000000000000000000000000000000", "1111111111111111111114oLvT2");
+	roundtrip(0x80, "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d", "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ");
+	roundtrip(0x05, "", "dDc8z6");
+}
+
+#[test]
+fn checksum() {
+	assert_eq!(Base58Btc.check().decode("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"), Err(Error::ChecksumMismatch));
+	assert_eq!(Base58Btc.check().decode("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfN"), Err(Error::ChecksumMismatch));
+	assert_eq!(Base58Btc.check().decode("1111"), Err(Error::IncorrectLength));
+	assert_eq!(Base58Btc.check().decode("1A1zP1eP5QGefi2DMPTfTL5SLmv7Divf0a"), Err(Error::InvalidCharacter { offset: 32, byte: b'0' }));
+}
+
+#[test]
+fn stack_buffers() {
+	let payload = [0xab; 32];
+	let mut stack_buf = [0u8; Base58Check::estimate_encoded_len(32)];
+	let encoded = Base58Btc.check().encode_into(0x80, &payload, &mut stack_buf);
+
+	let mut stack_buf = [0u8; 64];
+	let (version, decoded) = Base58Btc.check().decode_into(encoded, &mut stack_buf[..Base58Check::estimate_decoded_len(encoded.len())]).unwrap();
+	assert_eq!(version, 0x80);
+	assert_eq!(decoded, &payload);
+}
@@ -8,7 +8,7 @@ fn roundtrip(input: &[u8], encoding: &impl Encoding, expected: &str) {
 
 #[track_caller]
 fn error(string: &str, enc: &impl crate::Encoding, err: Error) {
-	let mut buf = [0u8; 64];
+	let mut buf = [0u8; 256];
 	assert_eq!(enc.decode_into(string.as_bytes(), &mut buf), Err(err));
 }
 
@@ -66,11 +66,11 @@ fn cwgem_test_base64_rb() {
 	error("AA", &base64std_strict, Error::IncorrectLength);
 	error("AA=", &base64std_strict, Error::IncorrectLength);
 	error("AA===", &base64std_strict, Error::IncorrectLength);
-	error("AA=x", &base64std_strict, Error::InvalidCharacter);
+	error("AA=x", &base64std_strict, Error::InvalidCharacter { offset: 2, byte: b'=' });
 	error("AAA", &base64std_strict, Error::IncorrectLength);
-	error("AAA^", &base64std_strict, Error::InvalidCharacter);
-	error("AB==", &base64std_strict, Error::NonCanonical);
-	error("AAB=", &base64std_strict, Error::NonCanonical);
+	error("AAA^", &base64std_strict, Error::InvalidCharacter { offset: 3, byte: b'^' });
+	error("AB==", &base64std_strict, Error::NonCanonical { offset: 0 });
+	error("AAB=", &base64std_strict, Error::NonCanonical { offset: 0 });
 
 	roundtrip(b"", &Base64Url, "");
 	roundtrip(b"\0", &Base64Url, "AA");
@@ -107,3 +107,57 @@ fn random() {
 	smash(&Base64Std.pad(NoPad), &mut stack_buf);
 	smash(&Base64Url.pad(NoPad), &mut stack_buf);
 }
+
+#[test]
+fn error_offsets() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 120];
+	rng.fill_bytes(&mut input);
+	let encoded = Base64Std.encode(&input);
+
+	for offset in 0..encoded.len() {
+		let mut string = encoded.clone().into_bytes();
+		string[offset] = b'*';
+		assert_eq!(Encoding::decode_into(&Base64Std, &string, Vec::new()), Err(Error::InvalidCharacter { offset, byte: b'*' }));
+	}
+
+	let string = "Zm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyAB";
+	assert_eq!(Base64Std.decode(string), Err(Error::NonCanonical { offset: 48 }));
+}
+
+#[test]
+fn strict_mode() {
+	let normal = Base64Std.pad(Padding::Strict);
+	let strict = Base64Std.pad(Padding::Strict).mode(Mode::Strict);
+
+	// Concatenated padded segments
+	assert_eq!(normal.decode("Zg==Zg==").unwrap(), b"ff");
+	error("Zg==Zg==", &strict, Error::InvalidCharacter { offset: 2, byte: b'=' });
+	error("Zm8=Zm9v", &strict, Error::InvalidCharacter { offset: 3, byte: b'=' });
+	error("Zm9vZg==Zm8=", &strict, Error::InvalidCharacter { offset: 6, byte: b'=' });
+
+	// Padding only in the final chunk
+	assert_eq!(strict.decode("Zg==").unwrap(), b"f");
+	assert_eq!(strict.decode("Zm9vYmFy").unwrap(), b"foobar");
+	assert_eq!(strict.decode("Zm9vYmE=").unwrap(), b"fooba");
+	let optional = Base64Std.pad(Padding::Optional).mode(Mode::Strict);
+	assert_eq!(optional.decode("Zm9vYmE").unwrap(), b"fooba");
+
+	// Long inputs exercising the SIMD code paths
+	let segment = "Zm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyZm9vYmE=";
+	let concat = segment.repeat(3);
+	assert_eq!(normal.decode(&concat).unwrap(), b"foobarfoobarfoobarfoobarfoobarfooba".repeat(3));
+	error(&concat, &strict, Error::InvalidCharacter { offset: segment.len() - 1, byte: b'=' });
+	assert_eq!(strict.decode(segment).unwrap(), b"foobarfoobarfoobarfoobarfoobarfooba");
+}
+
+#[test]
+fn lenient_mode() {
+	let lenient = Base64Std.pad(Padding::Optional).mode(Mode::Lenient);
+	assert_eq!(lenient.decode("AB==").unwrap(), b"\0");
+	assert_eq!(lenient.decode("AAB=").unwrap(), b"\0\0");
+	assert_eq!(lenient.decode("Zh").unwrap(), b"f");
+	assert_eq!(lenient.decode("Zm9=").unwrap(), b"fo");
+	assert_eq!(lenient.decode("Zm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyZm9vYmFyZm9vYh").unwrap(), b"foobarfoobarfoobarfoobarfoobarfoobarfoob");
+	error("A^==", &lenient, Error::InvalidCharacter { offset: 1, byte: b'^' });
+}
@@ -0,0 +1,65 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(input: &[u8], encoding: &impl Encoding, expected: &str) {
+	assert_eq!(expected, encoding.encode_into(input, String::new()));
+	assert_eq!(Ok(input), encoding.decode_into(expected.as_bytes(), Vec::new()).as_deref());
+}
+
+#[test]
+fn rfc1924() {
+	roundtrip(b"", &Base85Rfc1924, "");
+	roundtrip(b"h", &Base85Rfc1924, "Xa");
+	roundtrip(b"hello world", &Base85Rfc1924, "Xk~0{Zy<MXa%^M");
+	roundtrip(b"\x00\x00\x00\x00", &Base85Rfc1924, "00000");
+	roundtrip(b"\xff\xff\xff\xff", &Base85Rfc1924, "|NsC0");
+}
+
+#[test]
+fn errors() {
+	assert_eq!(Base85Rfc1924.decode("Xk~0{Z"), Err(Error::IncorrectLength));
+	assert_eq!(Base85Rfc1924.decode("Xk~0\""), Err(Error::InvalidCharacter { offset: 4, byte: b'"' }));
+	assert_eq!(Base85Rfc1924.decode("|NsC1"), Err(Error::InvalidCharacter { offset: 4, byte: b'1' }));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 1024];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+
+		let input = &input_buf[..len];
+		let encoded = Base85Rfc1924.encode(input);
+		assert_eq!(Base85Rfc1924.decode(&encoded).unwrap(), input);
+
+		let encoded = Base85Rfc1924.binary_patch().encode(input);
+		assert!(encoded.len() <= basenc::BinaryPatch::estimate_encoded_len(len));
+		assert_eq!(Base85Rfc1924.binary_patch().decode(&encoded).unwrap(), input);
+	}
+}
+
+#[test]
+fn binary_patch() {
+	let patch = Base85Rfc1924.binary_patch();
+	assert_eq!(patch.encode(b""), "");
+	assert_eq!(patch.encode(b"hello world"), "KXk~0{Zy<MXa%^M(\n");
+
+	let input: Vec<u8> = (0..60).collect();
+	let expected = "z009C61O)~M2nh-c3=Iws5D^j+6crX17#SKH9337XAR!_nBqb&%C@Cr{EG;fCFflSS\nHG&MFiI5|2y\n";
+	assert_eq!(patch.encode(&input), expected);
+	assert_eq!(patch.decode(expected).unwrap(), input);
+	assert_eq!(patch.decode(&expected.replace('\n', "\r\n")).unwrap(), input);
+	assert_eq!(patch.decode("KXk~0{Zy<MXa%^M(\n\n").unwrap(), b"hello world");
+}
+
+#[test]
+fn binary_patch_errors() {
+	let patch = Base85Rfc1924.binary_patch();
+	assert_eq!(patch.decode("PXk~0{Zy<MXa%^M(\n"), Err(Error::IncorrectLength));
+	assert_eq!(patch.decode("KXk~0{Zy<MXa%^M\n"), Err(Error::IncorrectLength));
+	assert_eq!(patch.decode("0Xk~0{Zy<MXa%^M(\n"), Err(Error::InvalidCharacter { offset: 0, byte: b'0' }));
+	assert_eq!(patch.decode("BXk~0{\nKXk~0\"Zy<MXa%^M(\n"), Err(Error::InvalidCharacter { offset: 12, byte: b'"' }));
+	assert_eq!(patch.decode("KXk~0{Zy<MXa%^M(\n\nBXk~0{\n"), Err(Error::InvalidCharacter { offset: 18, byte: b'B' }));
+}
@@ -0,0 +1,155 @@
+use basenc::*;
+
+// Reference implementation by Joachim Henke
+fn reference(bytes: &[u8]) -> String {
+	const CHARSET: &[u8; 91] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"";
+	let mut string = String::new();
+	let (mut b, mut n) = (0u32, 0u32);
+	for &byte in bytes {
+		b |= (byte as u32) << n;
+		n += 8;
+		if n > 13 {
+			let mut v = b & 8191;
+			if v > 88 {
+				b >>= 13;
+				n -= 13;
+			}
+			else {
+				v = b & 16383;
+				b >>= 14;
+				n -= 14;
+			}
+			string.push(CHARSET[(v % 91) as usize] as char);
+			string.push(CHARSET[(v / 91) as usize] as char);
+		}
+	}
+	if n > 0 {
+		string.push(CHARSET[(b % 91) as usize] as char);
+		if n > 7 || b > 90 {
+			string.push(CHARSET[(b / 91) as usize] as char);
+		}
+	}
+	string
+}
+
+#[track_caller]
+fn roundtrip(input: &[u8], expected: &str) {
+	assert_eq!(expected, Base91Std.encode(input));
+	assert_eq!(Ok(input), Base91Std.decode(expected).as_deref());
+}
+
+#[test]
+fn vectors() {
+	roundtrip(b"", "");
+	roundtrip(b"\x00", "AA");
+	roundtrip(b"a", "GB");
+	roundtrip(b"test", "fPNKd");
+	roundtrip(b"\xff\xff\xff", "B\"tW");
+	roundtrip(b"hello world", "TPwJh>Io2Tv!lE");
+	roundtrip(b"\x00\x01\x02\x03\x04\x05\x06\x07\x08\t\n\x0b\x0c\r\x0e\x0f", ":C#(:C?hVB$MSiVEwndB");
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 1024];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		// Sparse inputs exercise the 14-bit groups
+		if rng.coin_flip() {
+			for byte in &mut input_buf[..len] {
+				*byte &= 0x11;
+			}
+		}
+
+		let input = &input_buf[..len];
+		let encoded = Base91Std.encode(input);
+		assert_eq!(encoded, reference(input));
+		assert!(encoded.len() <= Base91::estimate_encoded_len(len));
+		assert_eq!(Base91Std.display(input).to_string(), encoded);
+
+		let decoded = Base91Std.decode(&encoded).unwrap();
+		assert_eq!(decoded, input);
+		assert!(decoded.len() <= Base91::estimate_decoded_len(encoded.len()));
+	}
+}
+
+#[test]
+fn streaming() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 1000];
+	rng.fill_bytes(&mut input);
+	let expected = Base91Std.encode(&input);
+
+	let mut stack_buf = [0u8; Base91::estimate_encoded_len(16)];
+	let mut encoder = Base91Std.encoder();
+	let mut encoded = String::new();
+	for chunk in input.chunks(16) {
+		encoded.push_str(encoder.update(chunk, &mut stack_buf));
+	}
+	encoded.push_str(encoder.finish(&mut stack_buf));
+	assert_eq!(encoded, expected);
+
+	let mut stack_buf = [0u8; Base91::estimate_decoded_len(7)];
+	let mut decoder = Base91Std.decoder();
+	let mut decoded = Vec::new();
+	for chunk in expected.as_bytes().chunks(7) {
+		let chunk = std::str::from_utf8(chunk).unwrap();
+		decoded.extend_from_slice(decoder.update(chunk, &mut stack_buf).unwrap());
+	}
+	decoded.extend_from_slice(decoder.finish(&mut stack_buf));
+	assert_eq!(decoded, input);
+}
+
+#[test]
+fn adapters() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 5000];
+	rng.fill_bytes(&mut input);
+	// Sparse bytes exercise the 14-bit groups
+	for byte in &mut input[..2500] {
+		*byte &= 0x11;
+	}
+	let expected = Base91Std.encode(&input);
+	assert_eq!(Base91Std.display(&input).to_string(), expected);
+
+	let mut encoded = Vec::new();
+	let mut writer = incremental::EncoderWriter::new(&Base91Std, &mut encoded);
+	for chunk in input.chunks(rng.range(1..700)) {
+		std::io::Write::write_all(&mut writer, chunk).unwrap();
+	}
+	writer.finish().unwrap();
+	assert_eq!(encoded, expected.as_bytes());
+
+	let mut reader = incremental::DecoderReader::new(&Base91Std, expected.as_bytes());
+	let mut decoded = Vec::new();
+	std::io::Read::read_to_end(&mut reader, &mut decoded).unwrap();
+	assert_eq!(decoded, input);
+
+	let mut reader = incremental::DecoderReader::new(&Base91Std, &b"TPwJh>Io2T v!lE"[..]);
+	let io_err = std::io::Read::read_to_end(&mut reader, &mut Vec::new()).unwrap_err();
+	assert_eq!(io_err.into_inner().unwrap().downcast_ref::<Error>(), Some(&Error::InvalidCharacter { offset: 10, byte: b' ' }));
+
+	// The ratio bounds encoding into a slice
+	for len in 0..100 {
+		let mut stack_buf = [0u8; 128];
+		let dest_len = Base91::RATIO.estimate_encoded_len(len);
+		let encoded = Encoding::encode_into(&Base91Std, &[0xff; 100][..len], &mut stack_buf[..dest_len]);
+		assert_eq!(encoded, reference(&[0xff; 100][..len]));
+	}
+}
+
+#[test]
+fn errors() {
+	assert_eq!(Base91Std.decode("TPwJh>Io 2Tv!lE"), Err(Error::InvalidCharacter { offset: 8, byte: b' ' }));
+	assert_eq!(Base91Std.decode("TPw'"), Err(Error::InvalidCharacter { offset: 3, byte: b'\'' }));
+
+	let mut decoder = Base91Std.decoder();
+	let mut bytes = Vec::new();
+	decoder.update("TPwJh", &mut bytes).unwrap();
+	assert_eq!(decoder.update("-", &mut bytes), Err(Error::InvalidCharacter { offset: 5, byte: b'-' }));
+	decoder.update(">Io2Tv!lE", &mut bytes).unwrap();
+	decoder.finish(&mut bytes);
+	assert_eq!(bytes, b"hello world");
+}
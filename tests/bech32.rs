@@ -0,0 +1,133 @@
+use basenc::*;
+
+#[test]
+fn bip173_valid() {
+	for string in [
+		"A12UEL5L",
+		"a12uel5l",
+		"an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+		"abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+		"split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+		"?1ezyfcl",
+	] {
+		let (hrp, values) = Bech32Std.decode_u5(string).unwrap();
+		assert_eq!(Bech32Std.encode_u5(hrp, &values), string.to_ascii_lowercase());
+		assert_eq!(Bech32m.decode_u5(string), Err(Error::ChecksumMismatch));
+	}
+}
+
+#[test]
+fn bip350_valid() {
+	for string in [
+		"A1LQFN3A",
+		"a1lqfn3a",
+		"abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+		"split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+		"?1v759aa",
+	] {
+		let (hrp, values) = Bech32m.decode_u5(string).unwrap();
+		assert_eq!(Bech32m.encode_u5(hrp, &values), string.to_ascii_lowercase());
+		assert_eq!(Bech32Std.decode_u5(string), Err(Error::ChecksumMismatch));
+	}
+}
+
+#[test]
+fn bip173_invalid() {
+	assert_eq!(Bech32Std.decode_u5("\x201nwldj5"), Err(Error::InvalidCharacter { offset: 0, byte: 0x20 }));
+	assert_eq!(Bech32Std.decode_u5("\x7f1axkwrx"), Err(Error::InvalidCharacter { offset: 0, byte: 0x7f }));
+	assert_eq!(Bech32Std.decode_u5("an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx"), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.decode_u5("pzry9x0s0muk"), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.decode_u5("1pzry9x0s0muk"), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.decode_u5("x1b4n0q5v"), Err(Error::InvalidCharacter { offset: 2, byte: b'b' }));
+	assert_eq!(Bech32Std.decode_u5("li1dgmt3"), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.decode_u5("A1G7SGD8"), Err(Error::ChecksumMismatch));
+	assert_eq!(Bech32Std.decode_u5("10a06t8"), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.decode_u5("1qzzfhee"), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.decode_u5("a12UEL5L"), Err(Error::InvalidCharacter { offset: 3, byte: b'U' }));
+}
+
+#[test]
+fn segwit() {
+	let program = b"\x75\x1e\x76\xe8\x19\x91\x96\xd4\x54\x94\x1c\x45\xd1\xb3\xa3\x23\xf1\x43\x3b\xd6";
+	assert_eq!(Bech32Std.encode_version("bc", 0, program), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+	assert_eq!(Bech32Std.decode_version("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4").unwrap(), ("BC", 0, program.to_vec()));
+
+	let program = [&program[..], &program[..]].concat();
+	let address = "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y";
+	assert_eq!(Bech32m.encode_version("bc", 1, &program), address);
+	assert_eq!(Bech32m.decode_version(address).unwrap(), ("bc", 1, program));
+
+	assert_eq!(Bech32Std.decode_version("a12uel5l"), Err(Error::IncorrectLength));
+}
+
+#[test]
+fn bytes() {
+	let key: Vec<u8> = (0..32).collect();
+	let encoded = "age1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0savhh7m";
+	assert_eq!(Bech32Std.encode("age", &key), encoded);
+	assert_eq!(Bech32Std.decode(encoded).unwrap(), ("age", key));
+
+	assert_eq!(Bech32Std.encode("test", b"hello"), "test1dpjkcmr09ys0qs");
+	assert_eq!(Bech32m.encode("test", b"hello"), "test1dpjkcmr0scqr9j");
+	assert_eq!(Bech32m.decode("test1dpjkcmr0scqr9j").unwrap(), ("test", b"hello".to_vec()));
+
+	// Too much padding
+	assert_eq!(Bech32Std.decode("lnbc1pzrlqpuymqta"), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.decode_u5("lnbc1pzrlqpuymqta").unwrap(), ("lnbc", vec![1, 2, 3, 31, 0, 1]));
+}
+
+#[test]
+fn limit() {
+	let values = [0u8; 100];
+	let encoded = Bech32Std.limit(1023).encode_u5("lnbc", &values);
+	assert_eq!(encoded.len(), 111);
+	assert_eq!(Bech32Std.decode_u5(&encoded), Err(Error::IncorrectLength));
+	assert_eq!(Bech32Std.limit(1023).decode_u5(&encoded).unwrap(), ("lnbc", values.to_vec()));
+}
+
+#[test]
+#[should_panic]
+fn limit_encode() {
+	Bech32Std.encode_u5("lnbc", &[0u8; 100]);
+}
+
+#[test]
+fn locate_errors() {
+	let mut rng = urandom::new();
+	let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+	assert_eq!(Bech32Std.locate_errors(address), None);
+
+	for _ in 0..100 {
+		let mut string = address.as_bytes().to_vec();
+		let first = rng.range(3..string.len());
+		let second = rng.range(3..string.len());
+		let offsets = if first == second { &[first][..] } else { &[first, second][..] };
+		for &offset in offsets {
+			let chr = string[offset];
+			while string[offset] == chr {
+				string[offset] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l"[rng.range(0..32)];
+			}
+		}
+		let string = std::str::from_utf8(&string).unwrap();
+		assert_eq!(Bech32Std.decode(string), Err(Error::ChecksumMismatch));
+
+		let expected = if first == second { (first, None) } else { (first.min(second), Some(first.max(second))) };
+		assert_eq!(Bech32Std.locate_errors(string), Some(expected));
+	}
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 50];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+
+		let input = &input_buf[..len];
+		let encoded = Bech32m.encode("hrp", input);
+		assert!(encoded.len() <= Bech32::estimate_encoded_len(3, len));
+		assert_eq!(Bech32m.decode(&encoded).unwrap(), ("hrp", input.to_vec()));
+		assert_eq!(Bech32m.decode(&encoded.to_ascii_uppercase()).unwrap(), ("HRP", input.to_vec()));
+	}
+}
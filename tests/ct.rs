@@ -0,0 +1,99 @@
+use basenc::*;
+
+#[track_caller]
+fn compare(regular: &impl Encoding, ct: &impl Encoding, input: &[u8]) {
+	let encoded = regular.encode_into(input, String::new());
+	assert_eq!(encoded, ct.encode_into(input, String::new()));
+	assert_eq!(Ok(input), ct.decode_into(encoded.as_bytes(), Vec::new()).as_deref());
+}
+
+fn smash(regular: &impl Encoding, ct: &impl Encoding) {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 256];
+
+	for _ in 0..200 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		compare(regular, ct, &input_buf[..len]);
+	}
+}
+
+#[test]
+fn random() {
+	for pad in [Padding::None, Padding::Optional, Padding::Strict] {
+		smash(&Base64Std.pad(pad), &ct::Base64Std.pad(pad));
+		smash(&Base64Url.pad(pad), &ct::Base64Url.pad(pad));
+		smash(&Base32Std.pad(pad), &ct::Base32Std.pad(pad));
+		smash(&Base32Hex.pad(pad), &ct::Base32Hex.pad(pad));
+		smash(&Base32Z.pad(pad), &ct::Base32Z.pad(pad));
+	}
+	smash(&Base64Url, &ct::Base64Url);
+	smash(&Base32Std, &ct::Base32Std);
+	smash(&LowerHex, &ct::LowerHex);
+	smash(&UpperHex, &ct::UpperHex);
+}
+
+#[test]
+fn errors() {
+	let strings = [
+		"Zg", "Zh", "Zg=", "Zg==", "Zg==Zg==", "Zm9=", "Z", "Zm9v*mFy", "Zm9vYmFy\n", "MY======", "MZXW6YQ=",
+		"MZXW6YR=", "MZXW6", "0080ffDC", "0080ffD", "00g0",
+	];
+	for string in strings {
+		for mode in [Mode::Normal, Mode::Strict, Mode::Lenient] {
+			for pad in [Padding::None, Padding::Optional, Padding::Strict] {
+				assert_eq!(Base64Std.pad(pad).mode(mode).decode(string), ct::Base64Std.pad(pad).mode(mode).decode(string));
+				assert_eq!(Base32Std.pad(pad).mode(mode).decode(string), ct::Base32Std.pad(pad).mode(mode).decode(string));
+			}
+		}
+		assert_eq!(LowerHex.decode(string), ct::LowerHex.decode(string));
+	}
+}
+
+#[test]
+fn padding_blocks() {
+	let strings = ["================", "==============00", "================================", "::::;;;;<<<<>>>>????????????????"];
+	for string in strings {
+		for mode in [Mode::Normal, Mode::Strict, Mode::Lenient] {
+			for pad in [Padding::None, Padding::Optional, Padding::Strict] {
+				let decoded = ct::Base64Std.pad(pad).mode(mode).decode(string);
+				assert!(decoded.is_err());
+				assert_eq!(Base64Std.pad(pad).mode(mode).decode(string), decoded);
+				let decoded = ct::Base32Std.pad(pad).mode(mode).decode(string);
+				assert!(decoded.is_err());
+				assert_eq!(Base32Std.pad(pad).mode(mode).decode(string), decoded);
+			}
+		}
+		let err = Error::InvalidCharacter { offset: 0, byte: string.as_bytes()[0] };
+		assert_eq!(LowerHex.decode(string), Err(err));
+		assert_eq!(UpperHex.decode(string), Err(err));
+		assert_eq!(ct::LowerHex.decode(string), Err(err));
+		assert_eq!(ct::UpperHex.decode(string), Err(err));
+	}
+}
+
+#[test]
+fn verify() {
+	assert!(ct::Base64Url.verify("c2VjcmV0", b"secret"));
+	assert!(!ct::Base64Url.verify("c2VjcmV0", b"secreT"));
+	assert!(!ct::Base64Url.verify("c2VjcmV0", b"secre"));
+	assert!(!ct::Base64Url.verify("c2VjcmV0", b"secrets"));
+	assert!(!ct::Base64Url.verify("c2VjcmV*", b"secret"));
+	assert!(ct::Base64Std.pad(Padding::Strict).verify("c2VjcmV0IGtleQ==", b"secret key"));
+	assert!(!ct::Base64Std.pad(Padding::Strict).verify("c2VjcmV0IGtleQ", b"secret key"));
+	assert!(ct::LowerHex.verify("00ff", b"\x00\xff"));
+	assert!(ct::Base32Std.verify("", b""));
+
+	// Padding before the final chunk
+	let concatenated = "Zg==".repeat(200);
+	assert!(ct::Base64Std.pad(Padding::Strict).verify(&concatenated, &[b'f'; 200]));
+	assert!(!ct::Base64Std.pad(Padding::Strict).mode(Mode::Strict).verify(&concatenated, &[b'f'; 200]));
+
+	let mut rng = urandom::new();
+	let mut key = [0u8; 2000];
+	rng.fill_bytes(&mut key);
+	let encoded = ct::Base32Hex.encode(&key);
+	assert!(ct::Base32Hex.verify(&encoded, &key));
+	key[1500] ^= 1;
+	assert!(!ct::Base32Hex.verify(&encoded, &key));
+}
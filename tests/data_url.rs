@@ -0,0 +1,79 @@
+use basenc::*;
+use basenc::data_url::{self, DataUrl};
+
+#[test]
+fn encoding() {
+	assert_eq!(DataUrl::new("text/plain").encode(b""), "data:text/plain,");
+	assert_eq!(DataUrl::new("").encode(b"hi"), "data:,hi");
+	assert_eq!(DataUrl::new("image/svg+xml").encode(b"<svg width='1'/>#"), "data:image/svg+xml,%3Csvg%20width=%271%27/%3E%23");
+	assert_eq!(DataUrl::new("application/octet-stream").base64().encode(b"\x00\xFF\xFE"), "data:application/octet-stream;base64,AP/+");
+	assert_eq!(DataUrl::new("text/plain;charset=UTF-8").base64().encode(b"ab"), "data:text/plain;charset=UTF-8;base64,YWI=");
+}
+
+#[test]
+fn media_type() {
+	let (media_type, _) = data_url::decode("data:text/html;charset=utf-8;;foo;lang=en,").unwrap();
+	assert_eq!(media_type.essence(), "text/html");
+	assert_eq!(media_type.params().collect::<Vec<_>>(), [("charset", "utf-8"), ("foo", ""), ("lang", "en")]);
+	assert_eq!(media_type.param("CHARSET"), Some("utf-8"));
+	assert_eq!(media_type.param("boundary"), None);
+
+	let (media_type, _) = data_url::decode("data:;charset=utf-8,").unwrap();
+	assert_eq!(media_type.essence(), "text/plain");
+	assert_eq!(media_type.param("charset"), Some("utf-8"));
+
+	let (media_type, decoded) = data_url::decode("DATA:image/png;BASE64,AAEC").unwrap();
+	assert_eq!(media_type.essence(), "image/png");
+	assert_eq!(media_type.params().count(), 0);
+	assert_eq!(decoded, b"\x00\x01\x02");
+
+	// Non-ASCII media types
+	let (media_type, decoded) = data_url::decode("data:€€€,x").unwrap();
+	assert_eq!(media_type.essence(), "€€€");
+	assert_eq!(decoded, b"x");
+	let (media_type, decoded) = data_url::decode("data:text/€;a=€;base64,eA==").unwrap();
+	assert_eq!(media_type.essence(), "text/€");
+	assert_eq!(media_type.param("a"), Some("€"));
+	assert_eq!(decoded, b"x");
+	assert_eq!(data_url::decode("data:€,x").unwrap().0.essence(), "€");
+
+	// Not a base64 marker
+	let (media_type, decoded) = data_url::decode("data:text/plain;base64x,AAEC").unwrap();
+	assert_eq!(media_type.param("base64x"), Some(""));
+	assert_eq!(decoded, b"AAEC");
+}
+
+#[test]
+fn decoding() {
+	assert_eq!(data_url::decode("data:,a,b%2C").unwrap().1, b"a,b,");
+	assert_eq!(data_url::decode("data:;base64,").unwrap().1, b"");
+
+	assert_eq!(data_url::decode(""), Err(Error::IncorrectLength));
+	assert_eq!(data_url::decode("dat"), Err(Error::IncorrectLength));
+	assert_eq!(data_url::decode("http://example.com"), Err(Error::InvalidCharacter { offset: 0, byte: b'h' }));
+	assert_eq!(data_url::decode("data:text/plain"), Err(Error::IncorrectLength));
+	assert_eq!(data_url::decode("data:text/plain,%4"), Err(Error::IncorrectLength));
+	assert_eq!(data_url::decode("data:text/plain,ab%zz"), Err(Error::InvalidCharacter { offset: 19, byte: b'z' }));
+	assert_eq!(data_url::decode("data:;base64,AA*A"), Err(Error::InvalidCharacter { offset: 15, byte: b'*' }));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 1024];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		let input = &input_buf[..len];
+
+		for url in [DataUrl::new("application/octet-stream"), DataUrl::new("font/woff2;x=y").base64()] {
+			let encoded = url.encode(input);
+			assert!(encoded.len() <= url.estimate_encoded_len(len));
+			assert_eq!(url.display(input).to_string(), encoded);
+
+			let mut decode_buf = [0u8; 4096];
+			let (_, decoded) = data_url::decode_into(&encoded, &mut decode_buf[..data_url::estimate_decoded_len(encoded.len())]).unwrap();
+			assert_eq!(decoded, input);
+		}
+	}
+}
@@ -0,0 +1,79 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(text: &str, expected: &str) {
+	assert_eq!(expected, encoded_word::encode(text));
+	assert_eq!(Ok(text.as_bytes()), encoded_word::decode(expected).as_deref());
+}
+
+#[test]
+fn scheme() {
+	roundtrip("", "");
+	roundtrip("a b", "=?UTF-8?Q?a_b?=");
+	roundtrip("a_b cd", "=?UTF-8?Q?a=5Fb_cd?=");
+	roundtrip("a_b=c?d", "=?UTF-8?B?YV9iPWM/ZA==?=");
+	roundtrip("Café au lait", "=?UTF-8?Q?Caf=C3=A9_au_lait?=");
+	roundtrip("Grüße", "=?UTF-8?B?R3LDvMOfZQ==?=");
+	roundtrip("日本語", "=?UTF-8?B?5pel5pys6Kqe?=");
+	roundtrip("\"Ünïcödé\" <ä>", "=?UTF-8?B?IsOcbsOvY8O2ZMOpIiA8w6Q+?=");
+}
+
+#[test]
+fn splitting() {
+	let text = "a".repeat(100);
+	roundtrip(&text, &format!("=?UTF-8?Q?{}?=\r\n =?UTF-8?Q?{}?=", &text[..63], &text[63..]));
+
+	let text = "é".repeat(40);
+	let encoded = encoded_word::encode(&text);
+	assert_eq!(encoded, format!("=?UTF-8?B?{}?=\r\n =?UTF-8?B?{}?=", Base64Std.pad(Padding::Strict).encode("é".repeat(22).as_bytes()), Base64Std.pad(Padding::Strict).encode("é".repeat(18).as_bytes())));
+	assert_eq!(encoded_word::decode(&encoded).unwrap(), text.as_bytes());
+
+	// Every encoded-word holds whole characters
+	let text = "aé€😀".repeat(20);
+	let encoded = encoded_word::encode(&text);
+	for word in encoded.split("\r\n ") {
+		assert!(word.len() <= 75);
+		let decoded = encoded_word::decode(word).unwrap();
+		assert!(std::str::from_utf8(&decoded).is_ok());
+	}
+	assert_eq!(encoded_word::decode(&encoded).unwrap(), text.as_bytes());
+}
+
+#[test]
+fn decoding() {
+	assert_eq!(encoded_word::decode("plain text").unwrap(), b"plain text");
+	assert_eq!(encoded_word::decode("=?UTF-8?Q?a?= =?UTF-8?Q?b?=").unwrap(), b"ab");
+	assert_eq!(encoded_word::decode("=?UTF-8?Q?a?=\r\n\t=?UTF-8?B?Yg==?=").unwrap(), b"ab");
+	assert_eq!(encoded_word::decode("=?UTF-8?Q?a?= b =?UTF-8?Q?c?=").unwrap(), b"a b c");
+	assert_eq!(encoded_word::decode("x =?UTF-8*en?Q?a?= ").unwrap(), b"x a ");
+	assert_eq!(encoded_word::decode("=?UTF-8?B?Yg?=").unwrap(), b"b");
+
+	// Malformed encoded-words are passed through
+	for text in ["=?UTF-8?Q?a b?=", "=??Q?a?=", "=?UTF-8?X?a?=", "=?UTF-8?Q?a", "=?"] {
+		assert_eq!(encoded_word::decode(text).unwrap(), text.as_bytes());
+	}
+	assert_eq!(encoded_word::decode("=?UTF-8?Q?a?= =?").unwrap(), b"a =?");
+
+	assert_eq!(encoded_word::decode("=?UTF-8?Q?=4G?="), Err(Error::InvalidCharacter { offset: 12, byte: b'G' }));
+	assert_eq!(encoded_word::decode("ab =?UTF-8?B?Y*==?="), Err(Error::InvalidCharacter { offset: 14, byte: b'*' }));
+	assert_eq!(encoded_word::decode("=?UTF-8?B?Y?="), Err(Error::IncorrectLength));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let chars = ['a', 'Z', '0', ' ', '_', '=', '?', '\t', 'é', '€', '😀'];
+	let mut text = String::new();
+	for _ in 0..1000 {
+		text.clear();
+		let len = rng.range(0..200);
+		for _ in 0..len {
+			text.push(chars[rng.range(0..chars.len())]);
+		}
+
+		let encoded = encoded_word::encode(&text);
+		assert!(encoded.len() <= encoded_word::estimate_encoded_len(text.len()));
+		assert!(encoded.split("\r\n ").all(|word| word.len() <= 75));
+		assert_eq!(encoded_word::decode(&encoded).unwrap(), text.as_bytes());
+	}
+}
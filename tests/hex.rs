@@ -47,3 +47,30 @@ fn random() {
 	smash(&LowerHex, &mut stack_buf);
 	smash(&UpperHex, &mut stack_buf);
 }
+
+#[test]
+fn error_offsets() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 120];
+	rng.fill_bytes(&mut input);
+	let encoded = LowerHex.encode(&input);
+
+	for offset in 0..encoded.len() {
+		let mut string = encoded.clone().into_bytes();
+		string[offset] = 0xFF;
+		assert_eq!(Encoding::decode_into(&LowerHex, &string, Vec::new()), Err(Error::InvalidCharacter { offset, byte: 0xFF }));
+	}
+}
+
+#[test]
+fn invalid_bytes() {
+	// Every non-hex byte is rejected, including by the vectorized decoders
+	for byte in (0..=255u8).filter(|byte| !byte.is_ascii_hexdigit()) {
+		for offset in [0, 7, 15, 16, 47] {
+			let mut string = [b'0'; 48];
+			string[offset] = byte;
+			assert_eq!(Encoding::decode_into(&LowerHex, &string, Vec::new()), Err(Error::InvalidCharacter { offset, byte }));
+			assert_eq!(Encoding::decode_into(&ct::LowerHex, &string, Vec::new()), Err(Error::InvalidCharacter { offset, byte }));
+		}
+	}
+}
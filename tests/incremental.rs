@@ -0,0 +1,177 @@
+use std::io::Write;
+use basenc::*;
+use basenc::incremental::*;
+
+#[track_caller]
+fn write_chunked(encoding: &impl Encoding, input: &[u8], chunk_size: usize) {
+	let mut writer = EncoderWriter::new(encoding, Vec::new());
+	for chunk in input.chunks(chunk_size) {
+		writer.write_all(chunk).unwrap();
+	}
+	let encoded = writer.finish().unwrap();
+	assert_eq!(encoding.encode_into(input, String::new()).as_bytes(), encoded);
+}
+
+#[test]
+fn encoder_writer() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 2000];
+	rng.fill_bytes(&mut input);
+
+	for &len in &[0, 1, 2, 3, 4, 5, 6, 7, 8, 100, 1000, 2000] {
+		for &chunk_size in &[1, 2, 3, 5, 7, 64, 513, 2000] {
+			let input = &input[..len];
+			write_chunked(&Base64Std, input, chunk_size);
+			write_chunked(&Base64Url.pad(Padding::Strict), input, chunk_size);
+			write_chunked(&Base32Std.pad(Padding::Strict), input, chunk_size);
+			write_chunked(&Base32Hex.pad(NoPad), input, chunk_size);
+			write_chunked(&LowerHex, input, chunk_size);
+			write_chunked(&UpperHex, input, chunk_size);
+		}
+	}
+}
+
+#[test]
+fn encoder_writer_drop() {
+	let encoding = Base64Std.pad(Padding::Strict);
+	let mut encoded = Vec::new();
+	{
+		let mut writer = EncoderWriter::new(&encoding, &mut encoded);
+		writer.write_all(b"f").unwrap();
+	}
+	assert_eq!(encoded, b"Zg==");
+}
+
+// Reader returning at most `step` bytes per read
+struct Trickle<'a> {
+	data: &'a [u8],
+	step: usize,
+}
+
+impl<'a> std::io::Read for Trickle<'a> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let len = self.data.len().min(self.step).min(buf.len());
+		buf[..len].copy_from_slice(&self.data[..len]);
+		self.data = &self.data[len..];
+		Ok(len)
+	}
+}
+
+#[track_caller]
+fn read_trickled(encoding: &impl Encoding, input: &[u8], step: usize) {
+	let encoded = encoding.encode_into(input, String::new());
+	let mut reader = DecoderReader::new(encoding, Trickle { data: encoded.as_bytes(), step });
+	let mut decoded = Vec::new();
+	std::io::Read::read_to_end(&mut reader, &mut decoded).unwrap();
+	assert_eq!(input, decoded);
+}
+
+#[track_caller]
+fn read_error(encoding: &impl Encoding, string: &str, err: Error) {
+	let mut reader = DecoderReader::new(encoding, Trickle { data: string.as_bytes(), step: 3 });
+	let mut decoded = Vec::new();
+	let io_err = std::io::Read::read_to_end(&mut reader, &mut decoded).unwrap_err();
+	assert_eq!(io_err.kind(), std::io::ErrorKind::InvalidData);
+	assert_eq!(io_err.into_inner().unwrap().downcast_ref::<Error>(), Some(&err));
+}
+
+#[test]
+fn decoder_reader() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 2000];
+	rng.fill_bytes(&mut input);
+
+	for &len in &[0, 1, 2, 3, 4, 5, 6, 7, 8, 100, 1000, 2000] {
+		for &step in &[1, 2, 3, 5, 7, 64, 513, 4000] {
+			let input = &input[..len];
+			read_trickled(&Base64Std, input, step);
+			read_trickled(&Base64Url.pad(Padding::Strict), input, step);
+			read_trickled(&Base32Std.pad(Padding::Strict), input, step);
+			read_trickled(&Base32Hex.pad(NoPad), input, step);
+			read_trickled(&LowerHex, input, step);
+			read_trickled(&UpperHex, input, step);
+		}
+	}
+}
+
+#[test]
+fn decoder_reader_errors() {
+	let base64std_strict = Base64Std.pad(Padding::Strict);
+	read_error(&base64std_strict, "Zm9vYmFyZm9vYmFyZm9vYmFyZg", Error::IncorrectLength);
+	read_error(&base64std_strict, "Zm9vYmFyZm9vYmFyZm9vYmFyAB==", Error::NonCanonical { offset: 24 });
+	read_error(&Base64Std, "Zm9vYmFy^m9vYmFyZm9vYmFy", Error::InvalidCharacter { offset: 8, byte: b'^' });
+	read_error(&LowerHex, "0011223", Error::IncorrectLength);
+}
+
+#[track_caller]
+fn update_chunked(encoding: &impl Encoding, input: &[u8], chunk_size: usize) {
+	let mut stack_buf = [0u8; 1024];
+
+	let mut encoder = Encoder::new(encoding);
+	let mut encoded = String::new();
+	for chunk in input.chunks(chunk_size) {
+		encoded.push_str(encoder.update(chunk, &mut stack_buf));
+	}
+	encoded.push_str(encoder.finish(&mut stack_buf));
+	assert_eq!(encoding.encode_into(input, String::new()), encoded);
+
+	let mut decoder = Decoder::new(encoding);
+	let mut decoded = Vec::new();
+	for chunk in encoded.as_bytes().chunks(chunk_size) {
+		decoded.extend_from_slice(decoder.update(chunk, &mut stack_buf).unwrap());
+	}
+	decoded.extend_from_slice(decoder.finish(&mut stack_buf).unwrap());
+	assert_eq!(input, decoded);
+}
+
+#[test]
+fn encoder_decoder() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 500];
+	rng.fill_bytes(&mut input);
+
+	for &len in &[0, 1, 2, 3, 4, 5, 6, 7, 8, 100, 500] {
+		for &chunk_size in &[1, 2, 3, 5, 7, 64, 500] {
+			let input = &input[..len];
+			update_chunked(&Base64Std, input, chunk_size);
+			update_chunked(&Base64Url.pad(Padding::Strict), input, chunk_size);
+			update_chunked(&Base32Std.pad(Padding::Strict), input, chunk_size);
+			update_chunked(&Base32Z.pad(NoPad), input, chunk_size);
+			update_chunked(&LowerHex, input, chunk_size);
+		}
+	}
+}
+
+#[test]
+fn decoder_holds_back_padding() {
+	let encoding = Base64Std.pad(Padding::Strict);
+	let mut stack_buf = [0u8; 16];
+	let mut decoder = Decoder::new(&encoding);
+	assert_eq!(decoder.update(b"Zm9v", &mut stack_buf), Ok(&b""[..]));
+	assert_eq!(decoder.update(b"Zg", &mut stack_buf), Ok(&b"foo"[..]));
+	assert_eq!(decoder.update(b"==", &mut stack_buf), Ok(&b""[..]));
+	assert_eq!(decoder.finish(&mut stack_buf), Ok(&b"f"[..]));
+}
+
+#[test]
+fn strict_padding_between_chunks() {
+	let strict = Base64Std.pad(Padding::Strict).mode(Mode::Strict);
+	let mut stack_buf = [0u8; 16];
+	let mut decoder = Decoder::new(&strict);
+	assert_eq!(decoder.update(b"Zg==", &mut stack_buf), Ok(&b""[..]));
+	assert_eq!(decoder.update(b"Zg==", &mut stack_buf), Err(Error::InvalidCharacter { offset: 2, byte: b'=' }));
+
+	let mut decoder = Decoder::new(&strict);
+	assert_eq!(decoder.update(b"Zm9vZg==", &mut stack_buf), Ok(&b"foo"[..]));
+	assert_eq!(decoder.update(b"Zg", &mut stack_buf), Err(Error::InvalidCharacter { offset: 6, byte: b'=' }));
+
+	read_error(&strict, "Zm9vZg==Zg==", Error::InvalidCharacter { offset: 6, byte: b'=' });
+	read_error(&Base32Std.pad(Padding::Strict).mode(Mode::Strict), "MY======MY======", Error::InvalidCharacter { offset: 2, byte: b'=' });
+
+	// Concatenated padded segments are accepted in normal mode
+	let normal = Base64Std.pad(Padding::Strict);
+	let mut decoder = Decoder::new(&normal);
+	assert_eq!(decoder.update(b"Zg==", &mut stack_buf), Ok(&b""[..]));
+	assert_eq!(decoder.update(b"Zg==", &mut stack_buf), Ok(&b"f"[..]));
+	assert_eq!(decoder.finish(&mut stack_buf), Ok(&b"f"[..]));
+}
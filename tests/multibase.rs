@@ -0,0 +1,101 @@
+use basenc::multibase::{self, Base};
+use basenc::Error;
+
+#[track_caller]
+fn roundtrip(base: Base, input: &[u8], expected: &str) {
+	assert_eq!(expected, multibase::encode(base, input));
+	assert_eq!(Ok((base, input.to_vec())), multibase::decode(expected));
+}
+
+#[test]
+fn vectors() {
+	let input = b"yes mani !";
+	roundtrip(Base::Base16Lower, input, "f796573206d616e692021");
+	roundtrip(Base::Base16Upper, input, "F796573206D616E692021");
+	roundtrip(Base::Base32Lower, input, "bpfsxgidnmfxgsibb");
+	roundtrip(Base::Base32Upper, input, "BPFSXGIDNMFXGSIBB");
+	roundtrip(Base::Base32HexLower, input, "vf5in683dc5n6i811");
+	roundtrip(Base::Base32HexUpper, input, "VF5IN683DC5N6I811");
+	roundtrip(Base::Base32Z, input, "hxf1zgedpcfzg1ebb");
+	roundtrip(Base::Base58Btc, input, "z7paNL19xttacUY");
+	roundtrip(Base::Base58Flickr, input, "Z7Pznk19XTTzBtx");
+	roundtrip(Base::Base64, input, "meWVzIG1hbmkgIQ");
+	roundtrip(Base::Base64Pad, input, "MeWVzIG1hbmkgIQ==");
+	roundtrip(Base::Base64Url, input, "ueWVzIG1hbmkgIQ");
+	roundtrip(Base::Base64UrlPad, input, "UeWVzIG1hbmkgIQ==");
+
+	let input = b"hello world";
+	roundtrip(Base::Base32PadLower, input, "cnbswy3dpeb3w64tmmq======");
+	roundtrip(Base::Base32PadUpper, input, "CNBSWY3DPEB3W64TMMQ======");
+	roundtrip(Base::Base32HexPadLower, input, "td1imor3f41rmusjccg======");
+	roundtrip(Base::Base32HexPadUpper, input, "TD1IMOR3F41RMUSJCCG======");
+	roundtrip(Base::Base32Z, input, "hpb1sa5dxrb5s6hucco");
+
+	roundtrip(Base::Base58Btc, b"", "z");
+	roundtrip(Base::Base64Pad, b"", "M");
+}
+
+#[test]
+fn codes() {
+	for code in 0..=255 {
+		if let Some(base) = Base::from_code(code) {
+			assert_eq!(base.code(), code);
+		}
+	}
+}
+
+#[test]
+fn errors() {
+	assert_eq!(multibase::decode(""), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("k2lcpzo5yikidynfl"), Err(Error::InvalidCharacter { offset: 0, byte: b'k' }));
+	assert_eq!(multibase::decode("\u{1F680}"), Err(Error::InvalidCharacter { offset: 0, byte: 0xf0 }));
+	assert_eq!(multibase::decode("BPFSXGIDNMFXGSIBB=="), Err(Error::InvalidCharacter { offset: 17, byte: b'=' }));
+	assert_eq!(multibase::decode("bPFSXGIDNMFXGSIBB"), Err(Error::InvalidCharacter { offset: 1, byte: b'P' }));
+	assert_eq!(multibase::decode("MeWVzIG1hbmkgIQ"), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("z0"), Err(Error::InvalidCharacter { offset: 1, byte: b'0' }));
+}
+
+#[test]
+fn slice_buffers() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 64];
+	rng.fill_bytes(&mut input);
+
+	for code in 0..=255 {
+		let Some(base) = Base::from_code(code) else { continue };
+		let mut encode_buf = [0u8; 256];
+		let encoded = multibase::encode_into(base, &input, &mut encode_buf[..multibase::estimate_encoded_len(base, 64)]);
+		let mut decode_buf = [0u8; 256];
+		let len = multibase::estimate_decoded_len(encoded.len());
+		let (detected, decoded) = multibase::decode_into(encoded, &mut decode_buf[..len]).unwrap();
+		assert_eq!(detected, base);
+		assert_eq!(decoded, input);
+	}
+}
+
+#[test]
+fn short_payloads() {
+	assert_eq!(multibase::decode("ba"), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("ca"), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("va"), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("h1"), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("f0"), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("ma"), Err(Error::IncorrectLength));
+	assert_eq!(multibase::decode("z2"), Ok((Base::Base58Btc, vec![1])));
+
+	for code in 0..=255 {
+		let Some(base) = Base::from_code(code) else { continue };
+		for len in 0..4 {
+			let encoded = multibase::encode(base, &[0xff; 3][..len]);
+			let mut decode_buf = [0u8; 16];
+			let len = multibase::estimate_decoded_len(encoded.len());
+			let (detected, _) = multibase::decode_into(&encoded, &mut decode_buf[..len]).unwrap();
+			assert_eq!(detected, base);
+
+			// Every one-character payload fits the estimate
+			let string = format!("{}{}", &encoded[..1], &encoded[1..encoded.len().min(2)]);
+			let len = multibase::estimate_decoded_len(string.len());
+			let _ = multibase::decode_into(&string, &mut decode_buf[..len]);
+		}
+	}
+}
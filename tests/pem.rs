@@ -0,0 +1,130 @@
+use basenc::*;
+use basenc::pem::{self, Pem, Profile};
+
+#[test]
+fn encoding() {
+	assert_eq!(Pem::new("EMPTY").encode(b""), "-----BEGIN EMPTY-----\n-----END EMPTY-----\n");
+
+	let bytes = [0xAB; 96];
+	let line = "q6ur".repeat(16);
+	let expected = format!("-----BEGIN X-----\r\n{line}\r\n{line}\r\n-----END X-----\r\n");
+	assert_eq!(Pem::new("X").line_ending(LineEnding::CrLf).encode(&bytes), expected);
+
+	let expected = format!("-----BEGIN X-----\n{line}\n{line}\nqw==\n-----END X-----\n");
+	assert_eq!(Pem::new("X").encode(&[0xAB; 97]), expected);
+}
+
+#[test]
+fn headers() {
+	let string = "-----BEGIN X-----\nProc-Type: 4,ENCRYPTED\nDEK-Info: DES-CBC,\n 0011223344556677\nComment:\n\nYQ==\n-----END X-----\n";
+	let (block, decoded) = pem::decode(string, Profile::Strict).unwrap();
+	assert_eq!(block.headers().collect::<Vec<_>>(), [("Proc-Type", "4,ENCRYPTED"), ("DEK-Info", "DES-CBC,\n 0011223344556677"), ("Comment", "")]);
+	assert_eq!(block.header("x"), None);
+	assert_eq!(decoded, b"a");
+
+	// Headers without data
+	let (block, decoded) = pem::decode("-----BEGIN X-----\r\nA: b\r\n-----END X-----", Profile::Strict).unwrap();
+	assert_eq!(block.header("a"), Some("b"));
+	assert_eq!(decoded, b"");
+
+	let headers = [("A", "b"), ("C", "d")];
+	let pem = Pem::new("X").headers(&headers);
+	let encoded = pem.encode(b"abc");
+	let (block, decoded) = pem::decode(&encoded, Profile::Strict).unwrap();
+	assert_eq!(block.headers().collect::<Vec<_>>(), headers);
+	assert_eq!(decoded, b"abc");
+}
+
+#[test]
+fn multiple_blocks() {
+	let string = "Certificate chain\n-----BEGIN CERTIFICATE-----\nYQ==\n-----END CERTIFICATE-----\nIntermediate\n\n-----BEGIN CERTIFICATE-----\nYmM=\n-----END CERTIFICATE-----\ntrailer";
+	let blocks = pem::decode_all(string, Profile::Strict).unwrap();
+	assert_eq!(blocks.len(), 2);
+	assert_eq!(blocks[0].1, b"a");
+	assert_eq!(blocks[1].1, b"bc");
+	assert_eq!(&string[blocks[1].0.end()..], "trailer");
+
+	assert_eq!(pem::decode_all("no blocks", Profile::Lax).unwrap().len(), 0);
+	assert_eq!(pem::decode("no blocks", Profile::Lax), Err(Error::IncorrectLength));
+
+	// Errors in later blocks point into the whole input
+	let string = "-----BEGIN A-----\nYQ==\n-----END A-----\n-----BEGIN B-----\nY*==\n-----END B-----\n";
+	assert_eq!(pem::decode_all(string, Profile::Lax), Err(Error::InvalidCharacter { offset: 58, byte: b'*' }));
+}
+
+#[test]
+fn lax() {
+	let string = "  -----BEGIN X-----  \n\tYW Jj\n\n ZA\n  -----END X-----\t";
+	let (block, decoded) = pem::decode(string, Profile::Lax).unwrap();
+	assert_eq!(block.label(), "X");
+	assert_eq!(block.end(), string.len());
+	assert_eq!(decoded, b"abcd");
+
+	assert_eq!(pem::decode("-----BEGIN X----- x\n-----END X-----", Profile::Lax), Err(Error::InvalidCharacter { offset: 18, byte: b'x' }));
+}
+
+#[test]
+fn strict() {
+	let line = "q6ur".repeat(16);
+
+	// Line too long
+	let string = format!("-----BEGIN X-----\n{line}q6ur\n-----END X-----\n");
+	assert_eq!(pem::decode(&string, Profile::Strict), Err(Error::InvalidCharacter { offset: 18 + 64, byte: b'q' }));
+	assert!(pem::decode(&string, Profile::Lax).is_ok());
+
+	// Short line followed by data
+	let string = format!("-----BEGIN X-----\nq6ur\n{line}\n-----END X-----\n");
+	assert_eq!(pem::decode(&string, Profile::Strict), Err(Error::InvalidCharacter { offset: 22, byte: b'\n' }));
+	assert!(pem::decode(&string, Profile::Lax).is_ok());
+
+	// Whitespace
+	let string = "-----BEGIN X-----\nq6 ur\n-----END X-----\n";
+	assert_eq!(pem::decode(string, Profile::Strict), Err(Error::InvalidCharacter { offset: 20, byte: b' ' }));
+	let string = "-----BEGIN X-----\nq6ur\n\n-----END X-----\n";
+	assert_eq!(pem::decode(string, Profile::Strict), Err(Error::InvalidCharacter { offset: 22, byte: b'\n' }));
+	let string = "-----BEGIN X----- \nq6ur\n-----END X-----\n";
+	assert_eq!(pem::decode(string, Profile::Strict), Err(Error::InvalidCharacter { offset: 17, byte: b' ' }));
+	let string = "-----BEGIN X-----\nq6ur\n -----END X-----\n";
+	assert_eq!(pem::decode(string, Profile::Strict), Err(Error::IncorrectLength));
+
+	// Padding
+	let string = "-----BEGIN X-----\nYQ\n-----END X-----\n";
+	assert_eq!(pem::decode(string, Profile::Strict), Err(Error::IncorrectLength));
+	assert_eq!(pem::decode(string, Profile::Lax).unwrap().1, b"a");
+}
+
+#[test]
+fn boundaries() {
+	assert_eq!(pem::decode("-----BEGIN X-----\nYQ==\n", Profile::Lax), Err(Error::IncorrectLength));
+	assert_eq!(pem::decode("-----BEGIN X\nYQ==\n-----END X-----", Profile::Lax), Err(Error::InvalidCharacter { offset: 12, byte: b'\n' }));
+	assert_eq!(pem::decode("-----BEGIN X-----\nYQ==\n-----END Y-----", Profile::Lax), Err(Error::InvalidCharacter { offset: 32, byte: b'Y' }));
+	assert_eq!(pem::decode("-----BEGIN X-----\nYQ==\n-----END X", Profile::Lax), Err(Error::IncorrectLength));
+	assert_eq!(pem::decode("-----BEGIN X-----\nYQ==\n-----END X--\n", Profile::Lax), Err(Error::InvalidCharacter { offset: 35, byte: b'\n' }));
+	assert_eq!(pem::decode("-----BEGIN X-----\nYQ*=\n-----END X-----", Profile::Lax), Err(Error::InvalidCharacter { offset: 20, byte: b'*' }));
+
+	// Empty label
+	let (block, decoded) = pem::decode("-----BEGIN -----\n-----END -----\n", Profile::Strict).unwrap();
+	assert_eq!((block.label(), &decoded[..]), ("", &b""[..]));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 1024];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		let input = &input_buf[..len];
+
+		for pem in [Pem::new("PRIVATE KEY"), Pem::new("CERTIFICATE").line_ending(LineEnding::CrLf)] {
+			let encoded = pem.encode(input);
+			assert!(encoded.len() <= pem.estimate_encoded_len(len));
+			assert_eq!(pem.display(input).to_string(), encoded);
+
+			let mut decode_buf = [0u8; 2048];
+			let (block, decoded) = pem::decode_into(&encoded, Profile::Strict, &mut decode_buf[..pem::estimate_decoded_len(encoded.len())]).unwrap();
+			assert_eq!(decoded, input);
+			assert_eq!(block.end(), encoded.len());
+		}
+	}
+}
@@ -0,0 +1,63 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(percent: &Percent, input: &[u8], expected: &str) {
+	assert_eq!(expected, percent.encode(input));
+	assert_eq!(Ok(input), percent.decode(expected).as_deref());
+}
+
+#[test]
+fn presets() {
+	let input = b"a b/c?d#e@f:g+h&i=j%k~l\"m[n]\xE2\x82\xAC";
+	roundtrip(&PercentPathSegment, input, "a%20b%2Fc%3Fd%23e@f:g+h&i=j%25k~l%22m%5Bn%5D%E2%82%AC");
+	roundtrip(&PercentQuery, input, "a%20b/c?d%23e@f:g+h&i=j%25k~l%22m%5Bn%5D%E2%82%AC");
+	roundtrip(&PercentFragment, input, "a%20b/c?d%23e@f:g+h&i=j%25k~l%22m%5Bn%5D%E2%82%AC");
+	roundtrip(&PercentUserinfo, input, "a%20b%2Fc%3Fd%23e%40f:g+h&i=j%25k~l%22m%5Bn%5D%E2%82%AC");
+	roundtrip(&PercentForm, input, "a+b%2Fc%3Fd%23e%40f%3Ag%2Bh%26i%3Dj%25k%7El%22m%5Bn%5D%E2%82%AC");
+}
+
+#[test]
+fn custom_set() {
+	let percent = Percent::new(AsciiSet::EMPTY);
+	roundtrip(&percent, b"ab", "%61%62");
+
+	// The percent sign is always escaped
+	let percent = Percent::new(AsciiSet::new(b"%a"));
+	roundtrip(&percent, b"a%", "a%25");
+
+	let set = AsciiSet::UNRESERVED.remove(b'~').add(b'/');
+	assert!(set.contains(b'/') && !set.contains(b'~') && !set.contains(0xff));
+	roundtrip(&Percent::new(set), b"/~", "/%7E");
+}
+
+#[test]
+fn decoding() {
+	assert_eq!(PercentQuery.decode("%e2%82%ac").unwrap(), "\u{20AC}".as_bytes());
+	assert_eq!(PercentQuery.decode("a+b").unwrap(), b"a+b");
+	assert_eq!(PercentForm.decode("a+b").unwrap(), b"a b");
+	assert_eq!(PercentQuery.decode("a b").unwrap(), b"a b");
+
+	assert_eq!(PercentQuery.decode("%"), Err(Error::IncorrectLength));
+	assert_eq!(PercentQuery.decode("%4"), Err(Error::IncorrectLength));
+	assert_eq!(PercentQuery.decode("%g"), Err(Error::InvalidCharacter { offset: 1, byte: b'g' }));
+	assert_eq!(PercentQuery.decode("ab%4g"), Err(Error::InvalidCharacter { offset: 4, byte: b'g' }));
+	assert_eq!(PercentQuery.decode("ab%%41"), Err(Error::InvalidCharacter { offset: 3, byte: b'%' }));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 1024];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+
+		let input = &input_buf[..len];
+		for percent in [&PercentPathSegment, &PercentForm] {
+			let encoded = percent.encode(input);
+			assert!(encoded.len() <= Percent::estimate_encoded_len(len));
+			assert_eq!(percent.display(input).to_string(), encoded);
+			assert_eq!(percent.decode(&encoded).unwrap(), input);
+		}
+	}
+}
@@ -0,0 +1,119 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(qp: &QuotedPrintable, input: &[u8], expected: &str) {
+	assert_eq!(expected, qp.encode(input));
+	assert_eq!(Ok(input), qp.decode(expected).as_deref());
+}
+
+#[test]
+fn escapes() {
+	let qp = QuotedPrintable::new(LineEnding::CrLf);
+	roundtrip(&qp, b"", "");
+	roundtrip(&qp, b"!\"#<=>}~", "!\"#<=3D>}~");
+	roundtrip(&qp, b"\x00\x7F\x80\xFF", "=00=7F=80=FF");
+	roundtrip(&qp, b"caf\xC3\xA9 au lait", "caf=C3=A9 au lait");
+	roundtrip(&qp, b"a\rb", "a=0Db");
+}
+
+#[test]
+fn trailing_whitespace() {
+	let qp = QuotedPrintable::new(LineEnding::CrLf);
+	roundtrip(&qp, b"a b ", "a b=20");
+	roundtrip(&qp, b"a\t", "a=09");
+	roundtrip(&qp, b"a  \r\nb\t\r\n", "a =20\r\nb=09\r\n");
+	roundtrip(&qp, b" \r\n", "=20\r\n");
+
+	// In binary mode the line break is escaped and the whitespace is not at the end of a line
+	let qp = qp.binary();
+	roundtrip(&qp, b"a \r\n", "a =0D=0A");
+}
+
+#[test]
+fn line_breaks() {
+	let qp = QuotedPrintable::new(LineEnding::CrLf);
+	assert_eq!(qp.encode(b"a\nb\r\nc\n"), "a\r\nb\r\nc\r\n");
+
+	let qp = QuotedPrintable::new(LineEnding::Lf);
+	assert_eq!(qp.encode(b"a\nb\r\nc\n"), "a\nb\nc\n");
+	roundtrip(&qp, b"a\n\nb", "a\n\nb");
+}
+
+#[test]
+fn soft_line_breaks() {
+	let qp = QuotedPrintable::new(LineEnding::CrLf);
+
+	let line = "a".repeat(76);
+	roundtrip(&qp, line.as_bytes(), &line);
+	roundtrip(&qp, format!("{line}\r\n").as_bytes(), &format!("{line}\r\n"));
+
+	let input = "a".repeat(100);
+	roundtrip(&qp, input.as_bytes(), &format!("{}=\r\n{}", &input[..75], &input[75..]));
+
+	// Escapes are not split across lines
+	let input = format!("{}\u{e9}", "a".repeat(72));
+	roundtrip(&qp, input.as_bytes(), &format!("{}=C3=\r\n=A9", "a".repeat(72)));
+
+	// Trailing whitespace escaped after the soft line break
+	let input = format!("{} ", "a".repeat(76));
+	roundtrip(&qp, input.as_bytes(), &format!("{}=\r\na=20", "a".repeat(75)));
+}
+
+#[test]
+fn decoding() {
+	let qp = QuotedPrintable::default();
+	assert_eq!(qp.decode("a=\nb=\r\nc").unwrap(), b"abc");
+	assert_eq!(qp.decode("a \t\r\nb  \nc ").unwrap(), b"a\r\nb\nc");
+	assert_eq!(qp.decode("a= \t\r\nb").unwrap(), b"ab");
+	assert_eq!(qp.decode("=e2=82=ac").unwrap(), "\u{20AC}".as_bytes());
+	assert_eq!(qp.decode("a=").unwrap(), b"a");
+	assert_eq!(qp.decode("=\n").unwrap(), b"");
+
+	assert_eq!(qp.decode("=4"), Err(Error::IncorrectLength));
+	assert_eq!(qp.decode("=g0"), Err(Error::InvalidCharacter { offset: 1, byte: b'g' }));
+	assert_eq!(qp.decode("ab=4g"), Err(Error::InvalidCharacter { offset: 4, byte: b'g' }));
+	assert_eq!(qp.decode("=4 \n"), Err(Error::InvalidCharacter { offset: 2, byte: b' ' }));
+	assert_eq!(qp.decode("=4\r\n"), Err(Error::InvalidCharacter { offset: 2, byte: b'\r' }));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 1024];
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		let input = &input_buf[..len];
+
+		let qp = QuotedPrintable::new(LineEnding::CrLf).binary();
+		let encoded = qp.encode(input);
+		assert!(encoded.len() <= QuotedPrintable::estimate_encoded_len(len));
+		assert!(encoded.split("\r\n").all(|line| line.len() <= 76));
+		assert_eq!(qp.decode(&encoded).unwrap(), input);
+
+		// Text without carriage returns survives a transport stripping trailing whitespace
+		for byte in &mut input_buf[..len] {
+			if *byte == b'\r' || (*byte >= 0x80 && *byte & 1 != 0) {
+				*byte = b' ';
+			}
+		}
+		let input = &input_buf[..len];
+
+		let qp = QuotedPrintable::new(LineEnding::Lf);
+		let encoded = qp.encode(input);
+		assert!(encoded.len() <= QuotedPrintable::estimate_encoded_len(len));
+		assert!(encoded.split('\n').all(|line| line.len() <= 76 && !line.ends_with([' ', '\t'])));
+		assert_eq!(qp.decode(&encoded).unwrap(), input);
+	}
+}
+
+#[test]
+fn stack_buffer() {
+	let qp = QuotedPrintable::new(LineEnding::CrLf);
+	let input = [0xFFu8; 100];
+	let mut encode_buf = [0u8; QuotedPrintable::estimate_encoded_len(100)];
+	let encoded = qp.encode_into(&input, &mut encode_buf);
+	let mut decode_buf = [0u8; 512];
+	let decoded = qp.decode_into(encoded, &mut decode_buf[..QuotedPrintable::estimate_decoded_len(encoded.len())]).unwrap();
+	assert_eq!(decoded, &input);
+}
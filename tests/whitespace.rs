@@ -0,0 +1,85 @@
+use basenc::*;
+
+// Inserts line breaks every `width` characters
+fn wrap(string: &str, width: usize, sep: &str) -> String {
+	let mut wrapped = String::new();
+	for line in string.as_bytes().chunks(width) {
+		wrapped.push_str(std::str::from_utf8(line).unwrap());
+		wrapped.push_str(sep);
+	}
+	wrapped
+}
+
+#[test]
+fn wrapped() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 2000];
+	rng.fill_bytes(&mut input);
+
+	for &len in &[0, 1, 2, 3, 4, 5, 57, 100, 2000] {
+		let input = &input[..len];
+		for &(width, sep) in &[(76, "\r\n"), (64, "\n"), (1, " "), (3, "\t \n")] {
+			let encoding = Base64Std.pad(Padding::Strict);
+			let wrapped = wrap(&encoding.encode(input), width, sep);
+			assert_eq!(encoding.skip_whitespace().decode(&wrapped).unwrap(), input);
+
+			let wrapped = wrap(&Base32Std.encode(input), width, sep);
+			assert_eq!(Base32Std.skip_whitespace().decode(&wrapped).unwrap(), input);
+
+			let wrapped = wrap(&LowerHex.encode(input), width, sep);
+			assert_eq!(LowerHex.skip_whitespace().decode(&wrapped).unwrap(), input);
+		}
+	}
+}
+
+#[test]
+fn scattered() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 300];
+	let whitespace = [b' ', b'\t', b'\n', b'\x0C', b'\r'];
+
+	for _ in 0..1000 {
+		let len = rng.range(0..input.len());
+		rng.fill_bytes(&mut input[..len]);
+		let input = &input[..len];
+
+		// Whitespace runs of random density and length between the characters
+		let density = rng.range(0..8);
+		let mut scattered = Vec::new();
+		for &chr in LowerHex.encode(input).as_bytes() {
+			while rng.range(0..8) < density {
+				scattered.push(whitespace[rng.range(0..whitespace.len())]);
+			}
+			scattered.push(chr);
+		}
+
+		let scattered = String::from_utf8(scattered).unwrap();
+		assert_eq!(LowerHex.skip_whitespace().decode(&scattered).unwrap(), input);
+	}
+}
+
+#[test]
+fn stack_buffer() {
+	let mut stack_buf = [0u8; 16];
+	let decoded = UpperHex.skip_whitespace().decode_into(" 00 80\n FF DC ", &mut stack_buf);
+	assert_eq!(decoded, Ok(&b"\x00\x80\xFF\xDC"[..]));
+}
+
+#[test]
+fn errors() {
+	let encoding = Base64Std.pad(Padding::Strict);
+	let decoded = encoding.skip_whitespace().decode("Zm9v\r\nYm*y\r\n");
+	assert_eq!(decoded, Err(Error::InvalidCharacter { offset: 8, byte: b'*' }));
+	let decoded = encoding.skip_whitespace().decode("Zm9v\r\nYmFy\r\nAB=\r\n=");
+	assert_eq!(decoded, Err(Error::NonCanonical { offset: 12 }));
+	let decoded = encoding.skip_whitespace().decode("Zm9v\r\nYmFy\r\nZg=\r\n");
+	assert_eq!(decoded, Err(Error::IncorrectLength));
+
+	let strict = Base64Std.pad(Padding::Strict).mode(Mode::Strict);
+	assert_eq!(strict.skip_whitespace().decode("Zg==Zg=="), Err(Error::InvalidCharacter { offset: 2, byte: b'=' }));
+	assert_eq!(strict.skip_whitespace().decode("Zm9v\r\nZg==\r\nZg"), Err(Error::InvalidCharacter { offset: 8, byte: b'=' }));
+	assert_eq!(encoding.skip_whitespace().decode("Zg==\r\nZg=="), Ok(b"ff".to_vec()));
+
+	let long = wrap(&"A".repeat(1000), 76, "\r\n") + "A*AA";
+	assert_eq!(Base64Std.skip_whitespace().decode(&long), Err(Error::InvalidCharacter { offset: long.len() - 3, byte: b'*' }));
+}
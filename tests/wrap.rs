@@ -0,0 +1,53 @@
+use basenc::*;
+
+// Reference implementation inserting separators between lines
+fn wrap(string: &str, width: usize, sep: &str) -> String {
+	let lines: Vec<&str> = string.as_bytes().chunks(width).map(|line| std::str::from_utf8(line).unwrap()).collect();
+	lines.join(sep)
+}
+
+#[track_caller]
+fn check<E: Encoding>(encoding: &E, wrapped: &Wrap<'_, impl Encoding>, input: &[u8], width: usize, ending: LineEnding) {
+	let expected = wrap(&encoding.encode_into(input, String::new()), width, ending.as_str());
+	assert_eq!(wrapped.encode(input), expected);
+	assert_eq!(wrapped.display(input).to_string(), expected);
+	assert_eq!(wrapped.decode(&expected).unwrap(), input);
+
+	let mut stack_buf = [0u8; 16384];
+	let len = E::RATIO.estimate_wrapped_len(input.len(), width, ending.as_str().len());
+	assert_eq!(wrapped.encode_into(input, &mut stack_buf[..len]), expected);
+}
+
+#[test]
+fn wrapped() {
+	let mut rng = urandom::new();
+	let mut input = [0u8; 1500];
+	rng.fill_bytes(&mut input);
+
+	for &len in &[0, 1, 2, 3, 56, 57, 58, 100, 1500] {
+		let input = &input[..len];
+		for &width in &[1, 3, 64, 76] {
+			for &ending in &[LineEnding::Lf, LineEnding::CrLf] {
+				let base64 = Base64Std.pad(Padding::Strict);
+				check(&base64, &base64.wrap(width, ending), input, width, ending);
+				check(&Base64Url, &Base64Url.wrap(width, ending), input, width, ending);
+				check(&Base32Hex, &Base32Hex.wrap(width, ending), input, width, ending);
+				check(&UpperHex, &UpperHex.wrap(width, ending), input, width, ending);
+			}
+		}
+	}
+}
+
+#[test]
+fn mime() {
+	let encoding = Base64Std.pad(Padding::Strict);
+	let encoded = encoding.wrap(76, LineEnding::CrLf).encode(&[0u8; 60]);
+	assert_eq!(encoded, format!("{}\r\n{}", "A".repeat(76), "AAAA"));
+}
+
+#[test]
+fn strict_padding() {
+	let encoding = Base64Std.pad(Padding::Strict).mode(Mode::Strict);
+	let decoded = encoding.wrap(4, LineEnding::CrLf).decode("Zm9v\r\nZg==\r\nZg==");
+	assert_eq!(decoded, Err(Error::InvalidCharacter { offset: 8, byte: b'=' }));
+}
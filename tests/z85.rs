@@ -0,0 +1,99 @@
+use basenc::*;
+
+#[track_caller]
+fn roundtrip(input: &[u8], encoding: &impl Encoding, expected: &str) {
+	assert_eq!(expected, encoding.encode_into(input, String::new()));
+	assert_eq!(Ok(input), encoding.decode_into(expected.as_bytes(), Vec::new()).as_deref());
+}
+
+#[test]
+fn rfc32() {
+	roundtrip(b"", &Z85, "");
+	roundtrip(b"\x86\x4F\xD2\x6F\xB5\x59\xF7\x5B", &Z85, "HelloWorld");
+	roundtrip(b"\0\0\0\0", &Z85, "00000");
+	roundtrip(b"\xff\xff\xff\xff", &Z85, "%nSc0");
+
+	// Test vectors from the reference implementation
+	let key = LowerHex.decode("8e0bdd697628b91d8f245587ee95c5b04d48963f79259877b49cd9063aead3b7").unwrap();
+	roundtrip(&key, &Z85, "JTKVSB%%)wK0E.X)V>+}o?pNmC{O&4W4b!Ni{Lh6");
+}
+
+#[test]
+fn padded() {
+	roundtrip(b"\x86", &Z85Padded, "H5");
+	roundtrip(b"\x86\x4F", &Z85Padded, "Hed");
+	roundtrip(b"\x86\x4F\xD2", &Z85Padded, "Helj");
+	roundtrip(b"\x86\x4F\xD2\x6F", &Z85Padded, "Hello");
+	roundtrip(b"\x86\x4F\xD2\x6F\xB5", &Z85Padded, "HelloWe");
+	assert_eq!(Z85Padded.decode("HelloW"), Err(Error::IncorrectLength));
+}
+
+#[test]
+fn errors() {
+	assert_eq!(Z85.decode("Helj"), Err(Error::IncorrectLength));
+	assert_eq!(Z85.decode("HelloWorl"), Err(Error::IncorrectLength));
+	assert_eq!(Z85.decode("Hello Worl"), Err(Error::InvalidCharacter { offset: 5, byte: b' ' }));
+	assert_eq!(Z85.decode("Hello~orld"), Err(Error::InvalidCharacter { offset: 5, byte: b'~' }));
+	assert_eq!(Z85.decode("%nSc1"), Err(Error::InvalidCharacter { offset: 4, byte: b'1' }));
+	assert_eq!(Z85Padded.decode("%nSd"), Err(Error::IncorrectLength));
+}
+
+#[test]
+#[should_panic(expected = "use Z85Padded")]
+fn encode_length() {
+	Z85.encode(b"abc");
+}
+
+#[test]
+#[should_panic(expected = "use Z85Padded")]
+fn encoder_incomplete_group() {
+	let mut stack_buf = [0u8; 16];
+	let mut encoder = incremental::Encoder::new(&Z85);
+	encoder.update(b"\x86\x4F\xD2\x6F\xB5", &mut stack_buf);
+	encoder.finish(&mut stack_buf);
+}
+
+#[test]
+fn adapters() {
+	let input = b"\x86\x4F\xD2\x6F\xB5\x59\xF7\x5B";
+	assert_eq!(Z85.display(input).to_string(), "HelloWorld");
+
+	let mut encoded = Vec::new();
+	let mut writer = incremental::EncoderWriter::new(&Z85, &mut encoded);
+	for chunk in input.chunks(3) {
+		std::io::Write::write_all(&mut writer, chunk).unwrap();
+	}
+	writer.finish().unwrap();
+	assert_eq!(encoded, b"HelloWorld");
+
+	assert_eq!(Z85.skip_whitespace().decode("Hello\r\nWorld").unwrap(), input);
+
+	let mut reader = incremental::DecoderReader::new(&Z85, &b"HelloWorld"[..]);
+	let mut decoded = Vec::new();
+	std::io::Read::read_to_end(&mut reader, &mut decoded).unwrap();
+	assert_eq!(decoded, input);
+
+	let mut reader = incremental::DecoderReader::new(&Z85, &b"HelloWorl"[..]);
+	let io_err = std::io::Read::read_to_end(&mut reader, &mut Vec::new()).unwrap_err();
+	assert_eq!(io_err.into_inner().unwrap().downcast_ref::<Error>(), Some(&Error::IncorrectLength));
+}
+
+#[test]
+fn random() {
+	let mut rng = urandom::new();
+	let mut input_buf = [0u8; 300];
+
+	for _ in 0..1000 {
+		let len = rng.range(0..input_buf.len());
+		rng.fill_bytes(&mut input_buf[..len]);
+		let input = &input_buf[..len];
+
+		let encoded = Z85Padded.encode(input);
+		assert_eq!(Z85Padded.decode(&encoded).unwrap(), input);
+		assert_eq!(Z85Padded.display(input).to_string(), encoded);
+
+		let input = &input[..len / 4 * 4];
+		assert_eq!(Z85.encode(input), &encoded[..len / 4 * 5]);
+		assert_eq!(Z85.decode(&encoded[..len / 4 * 5]).unwrap(), input);
+	}
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "zeroize")]
+
+use basenc::*;
+
+#[test]
+fn decode() {
+	let key = Base64Std.decode_into("c2VjcmV0IGtleQ==", Zeroizing::new()).unwrap();
+	assert_eq!(&key[..], b"secret key");
+
+	let mut buffer = Zeroizing::with_capacity(4);
+	assert_eq!(LowerHex.decode_into("0011", &mut buffer).unwrap(), b"\x00\x11");
+	// Grows and keeps the previous contents
+	assert_eq!(LowerHex.decode_into("2233445566778899", &mut buffer).unwrap(), b"\x22\x33\x44\x55\x66\x77\x88\x99");
+	assert_eq!(&buffer[..], b"\x00\x11\x22\x33\x44\x55\x66\x77\x88\x99");
+
+	buffer.clear();
+	assert!(buffer.is_empty());
+}
+
+#[test]
+fn encode() {
+	let encoded = Base64Url.encode_into(b"secret key", Zeroizing::new());
+	assert_eq!(encoded.to_str(), Ok("c2VjcmV0IGtleQ"));
+
+	let mut buffer = Zeroizing::new();
+	assert_eq!(UpperHex.encode_into(b"\xab", &mut buffer), "AB");
+	assert_eq!(UpperHex.encode_into(b"\xcd", &mut buffer), "CD");
+	assert_eq!(buffer.to_str(), Ok("ABCD"));
+	assert_eq!(format!("{:?}", buffer), "Zeroizing { len: 4, .. }");
+}